@@ -1,7 +1,9 @@
 use crate::serialization::prefab_loader::PrefabLoader;
+use crate::serialization::shared::ReferenceTable;
 use crate::utils::branch_likely_impl::unlikely;
 use crate::wire_type::{deformmat_wired_id, NonPrefabWireType, WireType};
 use crate::DeSerialization;
+use std::cell::Cell;
 
 struct DefaultPrefabLoader {}
 
@@ -29,6 +31,44 @@ pub enum DecodeError {
     PrefabToAnotherPrefab,
     AddOverflow,
     VersionNotCompatible,
+    InvalidBool,
+    /// A zero is decoded into a non zero integer.
+    InvalidNonZero,
+    /// A shared reference points to an allocation which does not exist or has another type.
+    InvalidReference,
+    /// A strong shared reference points to an allocation which is still being decoded.
+    ReferenceCycle,
+    /// The nanoseconds part of a time is not less than one second.
+    NanosOutOfRange,
+    /// A time can not be represented on this platform.
+    TimeOutOfRange,
+    /// A borrowed string is not valid UTF-8.
+    InvalidUtf8,
+    /// More elements are decoded than the capacity of a fixed capacity container.
+    CapacityExceeded,
+    /// The count of elements does not match the length of an array, see `ArrayLengthPolicy`.
+    ArrayLengthMismatch,
+    /// A field with the id is missing, and it has no default value.
+    MissingField(usize),
+}
+
+/// What to do when the count of encoded elements does not match the length of an array `[T; N]`,
+/// which happens when the length of an array field is changed between versions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArrayLengthPolicy {
+    /// Return `DecodeError::ArrayLengthMismatch`, the default.
+    Error,
+    /// Extra elements are decoded and dropped, fewer elements are still an error.
+    Truncate,
+    /// Missing elements are filled with `T::padding()`, usually `T::default()`, extra elements are
+    /// decoded and dropped. Padding a type without a padding value is an error.
+    Pad,
+}
+
+impl Default for ArrayLengthPolicy {
+    fn default() -> Self {
+        ArrayLengthPolicy::Error
+    }
 }
 
 /// Decode a wired id into an field id and wired type
@@ -55,77 +95,151 @@ pub fn decode_wired_id(
 /// Decode a field data into a value
 /// # Arguments
 /// * `is_prefab` - if true, the data will be sent to the prefab loader, to get the real data.
-pub fn decode_field<S: DeSerialization>(
+pub fn decode_field<'de, S: DeSerialization<'de>>(
     ptr: &mut *const u8,
-    ctx: &DecodeContext,
+    ctx: &DecodeContext<'de>,
     is_prefab: bool,
 ) -> Result<S, DecodeError> {
     if is_prefab {
-        unsafe {
-            let buffer = ctx.prefab_loader.handle_prefab(ptr, ctx)?;
-            let mut new_ptr = buffer.as_ptr();
-            let new_bounds_checker = BoundsChecker {
-                bound: new_ptr.add(buffer.len()),
-            };
-            let new_ctx = DecodeContext {
-                bounds_checker: new_bounds_checker,
-                prefab_loader: ctx.prefab_loader,
-            };
-            let v = S::decode(&mut new_ptr, &new_ctx)?;
-            Ok(v)
-        }
+        let buffer = ctx.prefab_loader.handle_prefab(ptr, ctx)?;
+        let mut new_ptr = buffer.as_ptr();
+        // decode the prefab data with the same context, bounded by the prefab buffer
+        let bound = ctx
+            .bounds_checker
+            .set_bound(unsafe { new_ptr.add(buffer.len()) });
+        let v = S::decode(&mut new_ptr, ctx);
+        ctx.bounds_checker.set_bound(bound);
+        v
+    } else if S::WIRE_TYPE == WireType::LengthDelimited {
+        decode_length_delimited(ptr, ctx)
     } else {
-        if S::WIRE_TYPE == WireType::LengthDelimited {
-            let size = usize::decode(ptr, ctx)?;
-            unsafe {
-                ctx.bounds_checker.check_bounds((*ptr).add(size))?;
-            }
-        }
-        let v = S::decode(ptr, ctx)?;
-        Ok(v)
+        S::decode(ptr, ctx)
     }
 }
 
+/// Decode a value encoded by `encode_nested`.
+pub fn decode_nested<'de, S: DeSerialization<'de>>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+) -> Result<S, DecodeError> {
+    if S::SELF_DELIMITED {
+        S::decode(ptr, ctx)
+    } else {
+        decode_length_delimited(ptr, ctx)
+    }
+}
+
+/// Decode a value which is prefixed by its size, the value can not read beyond that size.
+pub fn decode_length_delimited<'de, S: DeSerialization<'de>>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+) -> Result<S, DecodeError> {
+    decode_length_delimited_with(ptr, ctx, S::decode)
+}
+
+/// Like `decode_length_delimited`, but the data is decoded by `decode`, used by types which
+/// decode more than one value inside the size, e.g. the fields of a variant.
+pub fn decode_length_delimited_with<'de, T>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+    decode: impl FnOnce(&mut *const u8, &DecodeContext<'de>) -> Result<T, DecodeError>,
+) -> Result<T, DecodeError> {
+    let size = usize::decode(ptr, ctx)?;
+    let end = ctx.bounds_checker.end_of(*ptr, size)?;
+    let bound = ctx.bounds_checker.set_bound(end);
+    let v = decode(ptr, ctx);
+    ctx.bounds_checker.set_bound(bound);
+    // skip the data which is not recognized by the value
+    *ptr = end;
+    v
+}
+
 pub struct BoundsChecker {
-    bound: *const u8,
+    bound: Cell<*const u8>,
 }
 
 impl BoundsChecker {
     /// Get the boundary of the encoded data.
     pub fn get_bound(&self) -> *const u8 {
-        self.bound
+        self.bound.get()
+    }
+
+    /// Set the boundary of the encoded data, returns the previous one.
+    pub fn set_bound(&self, bound: *const u8) -> *const u8 {
+        self.bound.replace(bound)
     }
 
     /// Check if the given address is out of the boundary.
     pub fn check_bounds(&self, ptr: *const u8) -> Result<(), DecodeError> {
-        if unlikely(ptr.ge(&self.bound)) {
+        if unlikely(ptr.ge(&self.bound.get())) {
             Result::Err(DecodeError::DecodeOutOfBounds)
         } else {
             Result::Ok(())
         }
     }
+
+    /// Check if the end address of some data is out of the boundary, the end address itself is
+    /// not a part of the data, so it may equal to the boundary.
+    pub fn check_end(&self, end: *const u8) -> Result<(), DecodeError> {
+        if unlikely(end.gt(&self.bound.get())) {
+            Result::Err(DecodeError::DecodeOutOfBounds)
+        } else {
+            Result::Ok(())
+        }
+    }
+
+    /// Get the end address of `size` bytes starting at `start`, fails if they are out of the
+    /// boundary, without computing an address beyond it, as `size` may come from corrupted data.
+    pub fn end_of(&self, start: *const u8, size: usize) -> Result<*const u8, DecodeError> {
+        let remaining = (self.bound.get() as usize).saturating_sub(start as usize);
+        if unlikely(size > remaining) {
+            Result::Err(DecodeError::DecodeOutOfBounds)
+        } else {
+            Result::Ok(start.wrapping_add(size))
+        }
+    }
 }
 
 pub struct DecodeContext<'a> {
     pub bounds_checker: BoundsChecker,
     pub prefab_loader: &'a dyn PrefabLoader,
+    pub(crate) references: Option<ReferenceTable>,
+    pub array_length_policy: ArrayLengthPolicy,
 }
 
 impl<'a> DecodeContext<'a> {
+    /// Create a context to decode `data`, without shared references mode, and with the default
+    /// `ArrayLengthPolicy`.
+    pub fn new(data: &'a [u8], prefab_loader: &'a dyn PrefabLoader) -> Self {
+        DecodeContext {
+            bounds_checker: BoundsChecker {
+                bound: Cell::new(data.as_ptr_range().end),
+            },
+            prefab_loader,
+            references: None,
+            array_length_policy: ArrayLengthPolicy::default(),
+        }
+    }
+
     /// Skip the data, used when the field id is not recognized.
     pub fn skip(&self, ptr: &mut *const u8, wire_type: WireType) -> Result<(), DecodeError> {
+        let wire_type = match wire_type {
+            // skip prefab data as the type the prefab loader tells
+            WireType::Prefab => self.prefab_loader.skip_wire_type().to_wire_type(),
+            wire_type => wire_type,
+        };
         unsafe {
             match wire_type {
-                WireType::Bits8 => *ptr = ptr.add(1),
-                WireType::Bits16 => *ptr = ptr.add(2),
-                WireType::Bits32 => *ptr = ptr.add(4),
-                WireType::Bits64 => *ptr = ptr.add(8),
-                WireType::Bits128 => *ptr = ptr.add(16),
+                WireType::Bits8
+                | WireType::Bits16
+                | WireType::Bits32
+                | WireType::Bits64
+                | WireType::Bits128 => {
+                    *ptr = self.bounds_checker.end_of(*ptr, wire_type.fixed_size())?;
+                }
                 WireType::LengthDelimited => {
                     let size = usize::decode(ptr, self)?;
-                    let end = ptr.add(size);
-                    self.bounds_checker.check_bounds(end)?;
-                    *ptr = end;
+                    *ptr = self.bounds_checker.end_of(*ptr, size)?;
                 }
                 WireType::Varint => {
                     let mut p = *ptr;
@@ -137,35 +251,10 @@ impl<'a> DecodeContext<'a> {
                             p = p.add(1);
                         }
                     }
-                    *ptr = p;
+                    // the last byte belongs to the varint too
+                    *ptr = p.add(1);
                 }
-                // same as above, except checking prefab type
-                WireType::Prefab => match self.prefab_loader.skip_wire_type().to_wire_type() {
-                    WireType::Bits8 => *ptr = ptr.add(1),
-                    WireType::Bits16 => *ptr = ptr.add(2),
-                    WireType::Bits32 => *ptr = ptr.add(4),
-                    WireType::Bits64 => *ptr = ptr.add(8),
-                    WireType::Bits128 => *ptr = ptr.add(16),
-                    WireType::LengthDelimited => {
-                        let size = usize::decode(ptr, self)?;
-                        let end = ptr.add(size);
-                        self.bounds_checker.check_bounds(end)?;
-                        *ptr = end;
-                    }
-                    WireType::Varint => {
-                        let mut p = *ptr;
-                        loop {
-                            self.bounds_checker.check_bounds(p)?;
-                            if *p < 0x80 {
-                                break;
-                            } else {
-                                p = p.add(1);
-                            }
-                        }
-                        *ptr = p;
-                    }
-                    WireType::Prefab => return Err(DecodeError::PrefabToAnotherPrefab),
-                },
+                WireType::Prefab => return Err(DecodeError::PrefabToAnotherPrefab),
             }
             Ok(())
         }
@@ -185,28 +274,39 @@ impl<'a> Decoder<'a> {
 
     /// Create a decoder from a given data buffer, and a prefab loader
     pub fn from_data_with_preloader(data: &'a [u8], prefab_loader: &'a dyn PrefabLoader) -> Self {
-        unsafe {
-            let buf = data;
-            let bound = buf.as_ptr().add(buf.len());
-            Self {
-                buf,
-                ctx: DecodeContext {
-                    bounds_checker: BoundsChecker { bound },
-                    prefab_loader,
-                },
-            }
+        Self {
+            buf: data,
+            ctx: DecodeContext::new(data, prefab_loader),
         }
     }
 
+    /// Decode data encoded by `Encoder::from_shared`, so that values shared by `Rc`, `Arc` and
+    /// `Weak` are shared again after decoding.
+    pub fn with_shared_references(mut self) -> Self {
+        self.ctx.references = Some(ReferenceTable::default());
+        self
+    }
+
+    /// Set how to decode an array whose encoded length is different, `ArrayLengthPolicy::Error` by
+    /// default.
+    pub fn with_array_length_policy(mut self, policy: ArrayLengthPolicy) -> Self {
+        self.ctx.array_length_policy = policy;
+        self
+    }
+
     /// Decode the data into a value.
-    pub fn decode<S: DeSerialization>(&self) -> Result<S, DecodeError> {
+    pub fn decode<S: DeSerialization<'a>>(&self) -> Result<S, DecodeError> {
         let mut ptr = self.buf.as_ptr();
-        let v = S::decode(&mut ptr, &self.ctx)?;
-        Result::Ok(v)
+        let v = S::decode(&mut ptr, &self.ctx);
+        // references only live during one decoding
+        if let Some(references) = &self.ctx.references {
+            references.clear();
+        }
+        v
     }
 
     /// Get the context of a decoder, used for testing, you probably shouldn't use it
-    pub fn get_context(&self) -> &DecodeContext {
+    pub fn get_context(&self) -> &DecodeContext<'a> {
         &self.ctx
     }
 }
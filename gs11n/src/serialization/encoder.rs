@@ -1,14 +1,66 @@
 use crate::meta_data::Metadata;
+use crate::serialization::shared::SharedModeGuard;
 use crate::unsigned::EncodeSize;
 use crate::utils::branch_likely_impl::likely;
 use crate::wire_type::{WireType, WiredIdConstant};
 use crate::Serialization;
 use std::cell::Cell;
-use std::mem::MaybeUninit;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodeError {
+    /// A path or an OS string is not valid UTF-8.
+    InvalidUtf8,
+    /// A `Weak` which can be upgraded is encoded without shared references mode, where it can not
+    /// point to the value.
+    UnsharedWeak,
+    /// A `Weak` which can be upgraded points to a value which is not `LengthDelimited`, such values
+    /// are not tracked by shared references mode.
+    UntrackedWeak,
+}
+
+thread_local! {
+    /// The first error met in the current record pass.
+    static ENCODE_ERROR: Cell<Option<EncodeError>> = Cell::new(None);
+}
+
+/// Report a value which can not be encoded, called when the value is recorded, the value should
+/// then be recorded and encoded as if it is empty.
+pub(crate) fn report_encode_error(error: EncodeError) {
+    ENCODE_ERROR.with(|cell| {
+        let first = cell.take().unwrap_or(error);
+        cell.set(Some(first));
+    });
+}
+
+/// Collect the errors reported during a record pass, the errors of an outer pass (e.g. a value
+/// which creates an encoder while it is recorded) are restored when dropped, even on panic.
+struct EncodeErrorScope {
+    previous: Option<EncodeError>,
+}
+
+impl EncodeErrorScope {
+    fn new() -> Self {
+        EncodeErrorScope {
+            previous: ENCODE_ERROR.with(|cell| cell.take()),
+        }
+    }
+
+    fn error(&self) -> Option<EncodeError> {
+        ENCODE_ERROR.with(|cell| cell.get())
+    }
+}
+
+impl Drop for EncodeErrorScope {
+    fn drop(&mut self) {
+        ENCODE_ERROR.with(|cell| cell.set(self.previous));
+    }
+}
 
 pub struct Encoder<'a, S: Serialization> {
     value: &'a S,
     meta_data: Cell<Metadata>,
+    shared: bool,
+    error: Option<EncodeError>,
 }
 
 macro_rules! wired_id_constants {
@@ -94,33 +146,101 @@ pub fn encode_field<S: Serialization>(
     value.encode(ptr, meta_data);
 }
 
+/// Encode a value which has no field id, e.g. an element of a sequence, a value which is not
+/// self delimited is prefixed by its size.
+pub fn encode_nested<S: Serialization>(value: &S, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+    if !S::SELF_DELIMITED {
+        meta_data.size.encode_raw(ptr);
+    }
+    value.encode(ptr, meta_data);
+}
+
+/// Record the space needed of a value encoded by `encode_nested`.
+pub fn size_of_nested<S: Serialization>(metadata: &Metadata) -> usize {
+    if S::SELF_DELIMITED {
+        metadata.size
+    } else {
+        metadata.size.varint_size() + metadata.size
+    }
+}
+
 impl<'a, S: Serialization> Encoder<'a, S> {
     /// Create a encoder from a value
     pub fn from(value: &'a S) -> Self {
+        Self::new(value, false)
+    }
+
+    /// Create a encoder from a value, values shared by `Rc`, `Arc` and `Weak` are only encoded
+    /// once, so that their sharing (and cycles) can be restored by a decoder created with
+    /// `Decoder::with_shared_references`.
+    pub fn from_shared(value: &'a S) -> Self {
+        Self::new(value, true)
+    }
+
+    fn new(value: &'a S, shared: bool) -> Self {
         let mut meta_data = Metadata::default();
-        value.record(&mut meta_data);
+        let error = {
+            let errors = EncodeErrorScope::new();
+            let _guard = SharedModeGuard::new(shared);
+            value.record(&mut meta_data);
+            errors.error()
+        };
         Encoder {
             value,
             meta_data: Cell::new(meta_data),
+            shared,
+            error,
         }
     }
 
-    /// Encode the value into bytes
+    /// Encode the value into bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value can not be encoded, i.e. a path or an OS string which is not valid
+    /// UTF-8, or a `Weak` which can not point to its value. Use `try_encode` to get an
+    /// `EncodeError` instead when the value may contain such data.
     pub fn encode(&self) -> Vec<u8> {
-        let meta_data = self.meta_data.take();
-        let total_size = meta_data.size;
-        self.meta_data.replace(meta_data);
-
-        let mut buf = Vec::with_capacity(total_size);
-        unsafe {
-            buf.set_len(total_size);
+        match self.try_encode() {
+            Ok(buf) => buf,
+            Err(EncodeError::InvalidUtf8) => panic!(
+                "gs11n: a path or an OS string is not valid UTF-8, only UTF-8 paths and OS strings can be encoded"
+            ),
+            Err(EncodeError::UnsharedWeak) => panic!(
+                "gs11n: a `Weak` can only be encoded by an encoder created with `Encoder::from_shared`"
+            ),
+            Err(EncodeError::UntrackedWeak) => panic!(
+                "gs11n: a `Weak` can only point to a `LengthDelimited` value, which is tracked by shared references mode"
+            ),
         }
-        let mut ptr = buf.as_mut_ptr();
+    }
 
+    /// Encode the value into bytes, or return the first error met if the value can not be encoded,
+    /// e.g. a path which is not valid UTF-8.
+    pub fn try_encode(&self) -> Result<Vec<u8>, EncodeError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
         let mut meta_data = self.meta_data.take();
-        self.value.encode(&mut ptr, &mut meta_data);
+        let buf = {
+            let _guard = SharedModeGuard::new(self.shared);
+            encode_to_vec(self.value, &mut meta_data)
+        };
         self.meta_data.replace(meta_data);
+        Ok(buf)
+    }
+}
+
+/// Encode a recorded value into a new buffer.
+pub(crate) fn encode_to_vec<S: Serialization>(value: &S, meta_data: &mut Metadata) -> Vec<u8> {
+    let total_size = meta_data.size;
+    let mut buf = Vec::with_capacity(total_size);
+    let mut ptr = buf.as_mut_ptr();
+    value.encode(&mut ptr, meta_data);
 
-        buf
+    // all the `total_size` bytes are written by the encoding above
+    unsafe {
+        buf.set_len(total_size);
     }
+    buf
 }
@@ -11,12 +11,20 @@ pub struct Metadata {
     /// the size of represented data
     pub size: usize,
     children: FxHashMap<usize, Metadata>,
+    /// the encoded data of a value inside a cell, taken when it is recorded
+    pub(crate) snapshot: Option<Snapshot>,
+}
+
+/// The encoded data of a value, and the shared allocations met when encoding it, in order.
+pub(crate) struct Snapshot {
+    pub(crate) data: Vec<u8>,
+    pub(crate) allocations: Vec<usize>,
 }
 
 impl Metadata {
     /// get child metadata by id
     pub fn get(&mut self, index: usize) -> &mut Metadata {
-        self.children.entry(index).or_insert_with(Metadata::default);
+        self.children.entry(index).or_default();
         self.children.get_mut(&index).unwrap()
     }
 }
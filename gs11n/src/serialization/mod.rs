@@ -3,6 +3,7 @@ pub mod dynamic;
 pub mod encoder;
 pub mod meta_data;
 pub mod prefab_loader;
+pub mod shared;
 pub mod swap_bytes;
 pub mod wire_type;
 
@@ -13,6 +14,26 @@ use wire_type::WireType;
 /// Illustrate what kind of wire type a type is, check wire_type.rs for details
 pub trait WireTypeTrait {
     const WIRE_TYPE: WireType = WireType::LengthDelimited;
+
+    /// Whether a slice of this type can be encoded and decoded by copying its memory directly,
+    /// elements of other sequences are encoded as their memory too.
+    ///
+    /// This must only be true if every bit pattern of that size is a valid value, and the
+    /// in-memory representation of the type (on little endian hosts) is exactly its encoded data.
+    const BULK_COPY: bool = false;
+
+    /// Whether the end of the encoded data can be found from the data itself.
+    ///
+    /// A derived struct is not, its fields are read until the end of its data, so it is prefixed by
+    /// its size when it is nested without a field id, e.g. as an element of a sequence or a tuple.
+    /// A type which encodes another type as it is must forward the value of that type.
+    const SELF_DELIMITED: bool = true;
+
+    /// Whether this type is a byte, or a borrowed slice of bytes.
+    ///
+    /// A borrowed slice of bytes is encoded as the raw bytes, so it can be borrowed from the encoded
+    /// data when decoding, while owned sequences of bytes are encoded as sequences of varints.
+    const BYTES: bool = false;
 }
 
 /// This trait must be implemented if a type can be serialized.
@@ -57,7 +78,23 @@ pub trait Serialization: WireTypeTrait + Sized {
 /// So why not add the `Default` constraint? Because currently Default trait has problems on
 /// const generic arrays, check this discussion for details:
 /// `<https://users.rust-lang.org/t/implement-default-trait-on-const-genric-array/69894>`
-pub trait DeSerialization: WireTypeTrait + Sized {
+///
+/// `'de` is the lifetime of the encoded data, a type like `&'de str` borrows from the data instead
+/// of allocating, so it can only be decoded if the data outlives it.
+pub trait DeSerialization<'de>: WireTypeTrait + Sized {
     /// Get value from a given encoded data.
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError>;
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError>;
+
+    /// The value which fills the missing elements of an array decoded with
+    /// `ArrayLengthPolicy::Pad`, usually the default value. `None` if the type has no such value,
+    /// then padding an array of it fails with `DecodeError::ArrayLengthMismatch`.
+    fn padding() -> Option<Self> {
+        None
+    }
 }
+
+/// A type which can be decoded from data of any lifetime, which means it borrows nothing from the
+/// data. Use this as the bound of a generic type instead of `DeSerialization`.
+pub trait DeSerializationOwned: for<'de> DeSerialization<'de> {}
+
+impl<T> DeSerializationOwned for T where T: for<'de> DeSerialization<'de> {}
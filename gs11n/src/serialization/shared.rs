@@ -0,0 +1,236 @@
+//! Shared references mode, which keeps the identity of values shared by `Rc`, `Arc` and `Weak`.
+//!
+//! By default a shared pointer is encoded exactly like the value it points to, so a value shared
+//! by two pointers is encoded twice, and a cycle of pointers never ends. In shared references mode
+//! (`Encoder::from_shared` and `Decoder::with_shared_references`), every allocation gets an id
+//! when it is met the first time, and a reference is encoded as a varint tag followed by:
+//!
+//! * tag 0: nothing, the reference is null, e.g. a `Weak` which can not be upgraded.
+//! * tag 1: the size and data of the value, this is the first time the allocation is met.
+//! * tag n (n >= 2): nothing, the reference points to the allocation with id n - 2.
+//!
+//! Only allocations of `LengthDelimited` values are tracked, others are always encoded by value.
+//! A `Weak` can only point to a tracked allocation, so encoding a `Weak` which can be upgraded
+//! fails if the value is not tracked, or if not in shared references mode.
+use crate::decoder::{decode_length_delimited, DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::unsigned::EncodeSize;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use rustc_hash::FxHashMap;
+use std::any::Any;
+use std::cell::RefCell;
+
+const NULL_TAG: usize = 0;
+const NEW_TAG: usize = 1;
+const BACK_REFERENCE_TAG: usize = 2;
+
+/// Ids of the allocations met in an encoding pass, the id of an allocation is the index of its
+/// address in `addresses`.
+#[derive(Default)]
+struct AllocationIds {
+    ids: FxHashMap<usize, usize>,
+    addresses: Vec<usize>,
+}
+
+thread_local! {
+    /// Allocations met in the current encoding pass, `None` if not in shared mode.
+    static ALLOCATION_IDS: RefCell<Option<AllocationIds>> = RefCell::new(None);
+}
+
+/// Enable or disable shared references mode for an encoding pass (the record pass or the encode
+/// pass), both passes meet the allocations in the same order, so they assign the same ids.
+pub(crate) struct SharedModeGuard {
+    previous: Option<AllocationIds>,
+}
+
+impl SharedModeGuard {
+    pub(crate) fn new(enabled: bool) -> Self {
+        let ids = if enabled {
+            Some(AllocationIds::default())
+        } else {
+            None
+        };
+        SharedModeGuard {
+            previous: ALLOCATION_IDS.with(|cell| cell.replace(ids)),
+        }
+    }
+}
+
+impl Drop for SharedModeGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        ALLOCATION_IDS.with(|cell| cell.replace(previous));
+    }
+}
+
+pub(crate) fn is_shared_mode() -> bool {
+    ALLOCATION_IDS.with(|cell| cell.borrow().is_some())
+}
+
+/// Is an allocation of `T` encoded as a reference, the same for `Rc`, `Arc` and `Weak`.
+pub(crate) fn is_tracked<T: WireTypeTrait>() -> bool {
+    T::WIRE_TYPE == WireType::LengthDelimited && is_shared_mode()
+}
+
+/// Run `f` and forget the allocations it meets, so that recording a value again during the encode
+/// pass does not change the ids assigned by the encode pass.
+pub(crate) fn without_new_allocations<R>(f: impl FnOnce() -> R) -> R {
+    let met = ALLOCATION_IDS.with(|cell| cell.borrow().as_ref().map(|ids| ids.addresses.len()));
+    let r = f();
+    if let Some(met) = met {
+        ALLOCATION_IDS.with(|cell| {
+            let mut cell = cell.borrow_mut();
+            let ids = cell.as_mut().unwrap();
+            for address in ids.addresses.drain(met..) {
+                ids.ids.remove(&address);
+            }
+        });
+    }
+    r
+}
+
+/// Run `f` and get the allocations it meets the first time, in order.
+pub(crate) fn new_allocations<R>(f: impl FnOnce() -> R) -> (R, Vec<usize>) {
+    let met = ALLOCATION_IDS.with(|cell| cell.borrow().as_ref().map(|ids| ids.addresses.len()));
+    let r = f();
+    let allocations = match met {
+        Some(met) => {
+            ALLOCATION_IDS.with(|cell| cell.borrow().as_ref().unwrap().addresses[met..].to_vec())
+        }
+        None => Vec::new(),
+    };
+    (r, allocations)
+}
+
+/// Meet allocations again in another pass, without visiting their values, `allocations` are got
+/// by `new_allocations` at the same point of the previous pass.
+pub(crate) fn meet_allocations(allocations: &[usize]) {
+    for address in allocations {
+        ALLOCATION_IDS.with(|cell| {
+            let mut cell = cell.borrow_mut();
+            let ids = cell.as_mut().unwrap();
+            ids.ids.insert(*address, ids.addresses.len());
+            ids.addresses.push(*address);
+        });
+    }
+}
+
+/// Get the id of an allocation if it has been met, or assign a new one.
+fn visit<T>(value: &T) -> Option<usize> {
+    let address = value as *const T as usize;
+    ALLOCATION_IDS.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let ids = cell.as_mut().unwrap();
+        match ids.ids.get(&address) {
+            Some(id) => Some(*id),
+            None => {
+                ids.ids.insert(address, ids.addresses.len());
+                ids.addresses.push(address);
+                None
+            }
+        }
+    })
+}
+
+/// Record a reference to a shared allocation, `None` means a null reference.
+pub(crate) fn record_reference<T: Serialization>(target: Option<&T>, meta_data: &mut Metadata) {
+    meta_data.size = match target {
+        None => NULL_TAG.varint_size(),
+        Some(value) => match visit(value) {
+            Some(id) => (id + BACK_REFERENCE_TAG).varint_size(),
+            None => {
+                value.record(meta_data.get(0));
+                let size = meta_data.get(0).size;
+                NEW_TAG.varint_size() + size.varint_size() + size
+            }
+        },
+    }
+}
+
+/// Encode a reference to a shared allocation, `None` means a null reference.
+pub(crate) fn encode_reference<T: Serialization>(
+    target: Option<&T>,
+    ptr: &mut *mut u8,
+    meta_data: &mut Metadata,
+) {
+    match target {
+        None => NULL_TAG.encode_raw(ptr),
+        Some(value) => match visit(value) {
+            Some(id) => (id + BACK_REFERENCE_TAG).encode_raw(ptr),
+            None => {
+                NEW_TAG.encode_raw(ptr);
+                meta_data.get(0).size.encode_raw(ptr);
+                value.encode(ptr, meta_data.get(0));
+            }
+        },
+    }
+}
+
+/// What a reference tag refers to.
+#[derive(Clone, Copy)]
+pub(crate) enum Reference {
+    Null,
+    New,
+    Back(usize),
+}
+
+pub(crate) fn decode_tag(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext,
+) -> Result<Reference, DecodeError> {
+    Ok(match usize::decode(ptr, ctx)? {
+        NULL_TAG => Reference::Null,
+        NEW_TAG => Reference::New,
+        tag => Reference::Back(tag - BACK_REFERENCE_TAG),
+    })
+}
+
+/// Decode the value of an allocation met the first time.
+pub(crate) fn decode_value<'de, T: DeSerialization<'de>>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+) -> Result<T, DecodeError> {
+    decode_length_delimited(ptr, ctx)
+}
+
+/// Allocations met during decoding, indexed by id.
+///
+/// The pointer of an allocation is `P<UnsafeCell<MaybeUninit<T>>>` while its value is being
+/// decoded, and `P<T>` after that, where `P` is the pointer family, e.g. `Rc` or `Arc`.
+#[derive(Default)]
+pub struct ReferenceTable {
+    allocations: RefCell<Vec<Box<dyn Any>>>,
+}
+
+impl ReferenceTable {
+    /// Add an allocation whose value is not initialized yet, returns its id.
+    pub(crate) fn insert<P: Any>(&self, pointer: P) -> usize {
+        let mut allocations = self.allocations.borrow_mut();
+        allocations.push(Box::new(pointer));
+        allocations.len() - 1
+    }
+
+    /// Replace the pointer of an allocation after its value is initialized.
+    pub(crate) fn initialize<P: Any>(&self, id: usize, pointer: P) {
+        self.allocations.borrow_mut()[id] = Box::new(pointer);
+    }
+
+    /// Apply `f` on the pointer of an allocation.
+    pub(crate) fn with<R>(
+        &self,
+        id: usize,
+        f: impl FnOnce(&dyn Any) -> Result<R, DecodeError>,
+    ) -> Result<R, DecodeError> {
+        match self.allocations.borrow().get(id) {
+            Some(pointer) => f(&**pointer),
+            None => Err(DecodeError::InvalidReference),
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        // take the allocations out first, dropping them may drop other references
+        let allocations = std::mem::take(&mut *self.allocations.borrow_mut());
+        drop(allocations);
+    }
+}
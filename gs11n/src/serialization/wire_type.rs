@@ -9,7 +9,7 @@ use std::marker::PhantomData;
 /// number of bytes of data.
 /// `Prefab` is only used in decoding, which means the value must be passed to a prefab loader,
 /// to get the real encoded data. Check prefab_loader.rs for details.
-#[derive(PartialEq)]
+#[derive(Debug, PartialEq)]
 pub enum WireType {
     Bits8 = 0,
     Bits16 = 1,
@@ -38,7 +38,7 @@ impl WireType {
     }
 
     /// is the wired type a fixed type, which means the size is know at compile time.
-    pub fn is_fixed_type(&self) -> bool {
+    pub const fn is_fixed_type(&self) -> bool {
         match self {
             WireType::Bits8 => true,
             WireType::Bits16 => true,
@@ -50,6 +50,31 @@ impl WireType {
             WireType::LengthDelimited => false,
         }
     }
+
+    /// Get the size of a fixed wire type, 0 is returned if the wire type is not fixed.
+    pub const fn fixed_size(&self) -> usize {
+        match self {
+            WireType::Bits8 => 1,
+            WireType::Bits16 => 2,
+            WireType::Bits32 => 4,
+            WireType::Bits64 => 8,
+            WireType::Bits128 => 16,
+            _ => 0,
+        }
+    }
+
+    /// Get the fixed wire type which takes exactly `size` bytes, if there is no such fixed type,
+    /// `LengthDelimited` is returned.
+    pub const fn from_fixed_size(size: usize) -> WireType {
+        match size {
+            1 => WireType::Bits8,
+            2 => WireType::Bits16,
+            4 => WireType::Bits32,
+            8 => WireType::Bits64,
+            16 => WireType::Bits128,
+            _ => WireType::LengthDelimited,
+        }
+    }
 }
 
 pub struct WiredIdConstant<S: Serialization, const ID: u8> {
@@ -62,7 +87,9 @@ impl<S: Serialization, const ID: u8> WiredIdConstant<S, ID> {
 
 const fn wired_id_constant_from(id: u8, wire_type: WireType) -> u8 {
     if id > 0b11111 {
-        panic!("wired id constant is used for common use, and only support id which is less than 32");
+        panic!(
+            "wired id constant is used for common use, and only support id which is less than 32"
+        );
     }
     ((wire_type as u8) << 5) | id
 }
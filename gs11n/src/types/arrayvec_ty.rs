@@ -0,0 +1,36 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::types::slice::{decode_elements, encode_slice, record_slice};
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use arrayvec::ArrayVec;
+
+// same layout as a vector, so data can be shared between them
+impl<T, const CAP: usize> WireTypeTrait for ArrayVec<T, CAP> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T: Serialization, const CAP: usize> Serialization for ArrayVec<T, CAP> {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_slice(self.as_slice(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_slice(self.as_slice(), meta_data)
+    }
+}
+
+impl<'de, T: DeSerialization<'de>, const CAP: usize> DeSerialization<'de> for ArrayVec<T, CAP> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let mut vec = Self::new();
+        decode_elements(ptr, ctx, |element| {
+            vec.try_push(element)
+                .map_err(|_| DecodeError::CapacityExceeded)
+        })?;
+        Ok(vec)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
@@ -0,0 +1,175 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::slice::bulk_size;
+use crate::unsigned::EncodeSize;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::iter::FromIterator;
+
+/// The count of bytes which hold `len` values.
+fn byte_len(len: usize) -> usize {
+    len / 8 + usize::from(len % 8 != 0)
+}
+
+/// A `Vec<bool>` takes a byte for each value, which is a waste for large masks like visibility or
+/// fog of war. `BitVec` packs 8 values into a byte instead.
+///
+/// It is encoded as the count of values, followed by the packed bytes, the first value is the
+/// least significant bit of the first byte.
+/// ```
+/// use gs11n::bit_vec::BitVec;
+///
+/// let mut explored: BitVec = vec![true, false, true].into();
+/// explored.push(true);
+/// explored.set(1, true);
+/// assert_eq!(explored.len(), 4);
+/// assert!(explored.iter().all(|explored| explored));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    // bits after `len` are always 0, so that the derived traits work
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitVec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a `BitVec` of `len` values, all of them are `value`.
+    pub fn from_elem(len: usize, value: bool) -> Self {
+        let mut bits = BitVec {
+            bytes: vec![if value { 0xFF } else { 0 }; byte_len(len)],
+            len,
+        };
+        bits.clear_unused_bits();
+        bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the value at `index`, `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index < self.len {
+            Some(self.bytes[index / 8] & (1 << (index % 8)) != 0)
+        } else {
+            None
+        }
+    }
+
+    /// Set the value at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(
+            index < self.len,
+            "index {} is out of bounds of a BitVec with length {}",
+            index,
+            self.len
+        );
+        let mask = 1 << (index % 8);
+        if value {
+            self.bytes[index / 8] |= mask;
+        } else {
+            self.bytes[index / 8] &= !mask;
+        }
+    }
+
+    pub fn push(&mut self, value: bool) {
+        if self.len % 8 == 0 {
+            self.bytes.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |index| self.bytes[index / 8] & (1 << (index % 8)) != 0)
+    }
+
+    fn clear_unused_bits(&mut self) {
+        if self.len % 8 != 0 {
+            if let Some(last) = self.bytes.last_mut() {
+                *last &= (1 << (self.len % 8)) - 1;
+            }
+        }
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitVec::new();
+        for value in iter {
+            bits.push(value);
+        }
+        bits
+    }
+}
+
+impl From<&[bool]> for BitVec {
+    fn from(values: &[bool]) -> Self {
+        values.iter().copied().collect()
+    }
+}
+
+impl From<Vec<bool>> for BitVec {
+    fn from(values: Vec<bool>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl From<BitVec> for Vec<bool> {
+    fn from(bits: BitVec) -> Self {
+        bits.iter().collect()
+    }
+}
+
+impl WireTypeTrait for BitVec {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for BitVec {
+    fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+        self.len.encode_raw(ptr);
+        unsafe {
+            let p = *ptr;
+            std::ptr::copy_nonoverlapping(self.bytes.as_ptr(), p, self.bytes.len());
+            *ptr = p.add(self.bytes.len());
+        }
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = self.len.varint_size() + self.bytes.len();
+    }
+}
+
+impl<'de> DeSerialization<'de> for BitVec {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let len = usize::decode(ptr, ctx)?;
+        let size = byte_len(len);
+        let mut bits = unsafe {
+            let start = *ptr;
+            let end = start.add(bulk_size::<u8>(size, start, ctx)?);
+            *ptr = end;
+            BitVec {
+                bytes: std::slice::from_raw_parts(start, size).to_vec(),
+                len,
+            }
+        };
+        // unused bits are ignored
+        bits.clear_unused_bits();
+        Ok(bits)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
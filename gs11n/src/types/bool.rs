@@ -0,0 +1,46 @@
+use crate::decoder::DecodeContext;
+use crate::meta_data::Metadata;
+use crate::serialization::decoder::DecodeError;
+use crate::serialization::wire_type::WireType;
+use crate::serialization::{DeSerialization, Serialization, WireTypeTrait};
+use crate::utils::branch_likely_impl::unlikely;
+
+/// bool takes a single byte, which must be 0 or 1.
+///
+/// Notice that slices of bool are not bulk copied, because other bytes are not valid bool values.
+impl WireTypeTrait for bool {
+    const WIRE_TYPE: WireType = WireType::Bits8;
+}
+
+impl Serialization for bool {
+    fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+        unsafe {
+            let p = *ptr;
+            *p = *self as u8;
+            *ptr = p.add(1);
+        }
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = 1
+    }
+}
+
+impl<'de> DeSerialization<'de> for bool {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        unsafe {
+            let p = *ptr;
+            ctx.bounds_checker.check_bounds(p)?;
+            let byte = *p;
+            if unlikely(byte > 1) {
+                return Result::Err(DecodeError::InvalidBool);
+            }
+            *ptr = p.add(1);
+            Result::Ok(byte == 1)
+        }
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
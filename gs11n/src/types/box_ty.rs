@@ -5,9 +5,10 @@ use crate::{DeSerialization, Serialization, WireTypeTrait};
 
 impl<T> WireTypeTrait for Box<T>
 where
-    T: Sized,
+    T: Sized + WireTypeTrait,
 {
     const WIRE_TYPE: WireType = WireType::LengthDelimited;
+    const SELF_DELIMITED: bool = T::SELF_DELIMITED;
 }
 
 impl<T> Serialization for Box<T>
@@ -23,12 +24,16 @@ where
     }
 }
 
-impl<T> DeSerialization for Box<T>
+impl<'de, T> DeSerialization<'de> for Box<T>
 where
-    T: Sized + DeSerialization,
+    T: Sized + DeSerialization<'de>,
 {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         let t = T::decode(ptr, ctx)?;
         Ok(Box::new(t))
     }
+
+    fn padding() -> Option<Self> {
+        T::padding().map(Box::new)
+    }
 }
@@ -0,0 +1,106 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::encoder::encode_to_vec;
+use crate::meta_data::{Metadata, Snapshot};
+use crate::serialization::shared::{meet_allocations, new_allocations, without_new_allocations};
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::cell::{Cell, RefCell};
+use std::sync::{Mutex, RwLock};
+
+thread_local! {
+    /// A snapshot of a cell is being taken, the cells inside it are a part of that snapshot.
+    static IN_SNAPSHOT: Cell<bool> = Cell::new(false);
+}
+
+/// Mark that a snapshot is being taken until it is dropped.
+struct SnapshotGuard;
+
+impl SnapshotGuard {
+    fn new() -> Self {
+        IN_SNAPSHOT.with(|cell| cell.set(true));
+        SnapshotGuard
+    }
+}
+
+impl Drop for SnapshotGuard {
+    fn drop(&mut self) {
+        IN_SNAPSHOT.with(|cell| cell.set(false));
+    }
+}
+
+/// A value inside a cell can be changed after it is recorded, so it is encoded (while it is
+/// borrowed or locked) when it is recorded, and the encoded data is written when encoding.
+/// A cell inside another cell is encoded as a part of the outer snapshot, without its own.
+fn record_snapshot<T: Serialization>(value: &T, meta_data: &mut Metadata) {
+    if IN_SNAPSHOT.with(|cell| cell.get()) {
+        value.record(meta_data);
+        return;
+    }
+    let _guard = SnapshotGuard::new();
+    let mut value_meta_data = Metadata::default();
+    // the allocations are met again when the value is encoded below
+    without_new_allocations(|| value.record(&mut value_meta_data));
+    let (data, allocations) = new_allocations(|| encode_to_vec(value, &mut value_meta_data));
+    meta_data.size = data.len();
+    meta_data.snapshot = Some(Snapshot { data, allocations });
+}
+
+/// Write the snapshot taken by `record_snapshot`, returns false if there is no snapshot, then the
+/// cell is inside another snapshot, and the value should be encoded directly.
+fn encode_snapshot(ptr: &mut *mut u8, meta_data: &mut Metadata) -> bool {
+    let snapshot = match &meta_data.snapshot {
+        Some(snapshot) => snapshot,
+        None => return false,
+    };
+    meet_allocations(&snapshot.allocations);
+    unsafe {
+        let p = *ptr;
+        std::ptr::copy_nonoverlapping(snapshot.data.as_ptr(), p, snapshot.data.len());
+        *ptr = p.add(snapshot.data.len());
+    }
+    true
+}
+
+// cells are encoded exactly like the value inside, as it is when the encoder is created.
+macro_rules! s11n_for_cell {
+    ($($c:ident $(where T: $bound:path)?, $cell:ident => $borrow:expr;)*) => ($(
+        impl<T: WireTypeTrait> WireTypeTrait for $c<T> {
+            const WIRE_TYPE: WireType = T::WIRE_TYPE;
+            const SELF_DELIMITED: bool = T::SELF_DELIMITED;
+        }
+
+        /// The value is borrowed or locked when the encoder is created, so a `RefCell` which is
+        /// mutably borrowed, or a lock which is poisoned, causes a panic, and a `Mutex` or an
+        /// `RwLock` which is locked for writing by the current thread deadlocks (or panics).
+        impl<T: Serialization $(+ $bound)?> Serialization for $c<T> {
+            fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+                if !encode_snapshot(ptr, meta_data) {
+                    let $cell = self;
+                    $borrow.encode(ptr, meta_data);
+                }
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                let $cell = self;
+                record_snapshot(&$borrow, meta_data);
+            }
+        }
+
+        impl<'de, T: DeSerialization<'de>> DeSerialization<'de> for $c<T> {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                Ok($c::new(T::decode(ptr, ctx)?))
+            }
+
+            fn padding() -> Option<Self> {
+                T::padding().map($c::new)
+            }
+        }
+    )*)
+}
+
+s11n_for_cell! {
+    Cell where T: Copy, cell => cell.get();
+    RefCell, cell => *cell.borrow();
+    Mutex, cell => *cell.lock().expect("gs11n: the mutex is poisoned");
+    RwLock, cell => *cell.read().expect("gs11n: the lock is poisoned");
+}
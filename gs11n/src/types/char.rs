@@ -42,8 +42,8 @@ impl Serialization for char {
     }
 }
 
-impl DeSerialization for char {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+impl<'de> DeSerialization<'de> for char {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         unsafe {
             let p = *ptr;
             let mut value = MaybeUninit::<char>::uninit();
@@ -59,4 +59,8 @@ impl DeSerialization for char {
             Result::Ok(value)
         }
     }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
 }
@@ -0,0 +1,174 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::types::slice::{decode_elements, encode_elements, record_elements};
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::collections::{BTreeSet, BinaryHeap, HashSet, LinkedList, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+impl<T, S> WireTypeTrait for HashSet<T, S> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T, S> Serialization for HashSet<T, S>
+where
+    T: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_elements(self.len(), self.iter(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_elements(self.len(), self.iter(), meta_data)
+    }
+}
+
+impl<'de, T, S> DeSerialization<'de> for HashSet<T, S>
+where
+    T: DeSerialization<'de> + std::cmp::Eq + Hash,
+    S: Default + BuildHasher,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let mut set = Self::default();
+        decode_elements(ptr, ctx, |element| {
+            set.insert(element);
+            Ok(())
+        })?;
+        Ok(set)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+impl<T> WireTypeTrait for BTreeSet<T> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T> Serialization for BTreeSet<T>
+where
+    T: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_elements(self.len(), self.iter(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_elements(self.len(), self.iter(), meta_data)
+    }
+}
+
+impl<'de, T> DeSerialization<'de> for BTreeSet<T>
+where
+    T: DeSerialization<'de> + std::cmp::Ord,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let mut set = Self::default();
+        decode_elements(ptr, ctx, |element| {
+            set.insert(element);
+            Ok(())
+        })?;
+        Ok(set)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+impl<T> WireTypeTrait for VecDeque<T> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T> Serialization for VecDeque<T>
+where
+    T: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_elements(self.len(), self.iter(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_elements(self.len(), self.iter(), meta_data)
+    }
+}
+
+impl<'de, T> DeSerialization<'de> for VecDeque<T>
+where
+    T: DeSerialization<'de>,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        // same layout as a vector, so reuse it to take the bulk copy path
+        Ok(Vec::<T>::decode(ptr, ctx)?.into())
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+impl<T> WireTypeTrait for BinaryHeap<T> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T> Serialization for BinaryHeap<T>
+where
+    T: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_elements(self.len(), self.iter(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_elements(self.len(), self.iter(), meta_data)
+    }
+}
+
+impl<'de, T> DeSerialization<'de> for BinaryHeap<T>
+where
+    T: DeSerialization<'de> + std::cmp::Ord,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        Ok(Vec::<T>::decode(ptr, ctx)?.into())
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+impl<T> WireTypeTrait for LinkedList<T> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T> Serialization for LinkedList<T>
+where
+    T: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_elements(self.len(), self.iter(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_elements(self.len(), self.iter(), meta_data)
+    }
+}
+
+impl<'de, T> DeSerialization<'de> for LinkedList<T>
+where
+    T: DeSerialization<'de>,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let mut list = Self::new();
+        decode_elements(ptr, ctx, |element| {
+            list.push_back(element);
+            Ok(())
+        })?;
+        Ok(list)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
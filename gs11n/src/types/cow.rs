@@ -0,0 +1,53 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::slice::decode_bytes;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::borrow::Cow;
+
+// a `Cow` is encoded exactly like a reference to the borrowed type, and always decoded as owned
+
+impl<'a, B> WireTypeTrait for Cow<'a, B>
+where
+    B: ?Sized + ToOwned + 'a,
+    for<'b> &'b B: WireTypeTrait,
+{
+    const WIRE_TYPE: WireType = <&'a B>::WIRE_TYPE;
+    const SELF_DELIMITED: bool = <&'a B>::SELF_DELIMITED;
+}
+
+impl<'a, B> Serialization for Cow<'a, B>
+where
+    B: ?Sized + ToOwned + 'a,
+    for<'b> &'b B: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        (&**self).encode(ptr, meta_data);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        (&**self).record(meta_data);
+    }
+}
+
+impl<'de, 'a, B> DeSerialization<'de> for Cow<'a, B>
+where
+    B: ?Sized + ToOwned + 'a,
+    for<'b> &'b B: WireTypeTrait,
+    B::Owned: DeSerialization<'de>,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        if <&'a B>::BYTES {
+            // `B` is `[u8]`, whose references are encoded as raw bytes rather than as its owned type
+            let bytes = decode_bytes(ptr, ctx)?;
+            let borrowed: &B = unsafe { std::mem::transmute_copy(&bytes) };
+            return Ok(Cow::Owned(borrowed.to_owned()));
+        }
+        let owned = B::Owned::decode(ptr, ctx)?;
+        Ok(Cow::Owned(owned))
+    }
+
+    fn padding() -> Option<Self> {
+        B::Owned::padding().map(Cow::Owned)
+    }
+}
@@ -0,0 +1,87 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
+
+/// Integers are encoded as varint by default, which is compact for small values like counters, but
+/// wastes space for values which are evenly distributed, like GUIDs, hashes, random seeds or
+/// bitmasks.
+///
+/// Wrap such a value with `Fixed` to encode it with a fixed wire type in little endian instead,
+/// a slice of `Fixed` values is copied as a whole. `usize` and `isize` are not supported, since
+/// their sizes depend on the platform.
+/// ```
+/// use gs11n::fixed::Fixed;
+///
+/// let guid = Fixed(0x0123_4567_89ab_cdef_0123_4567_89ab_cdefu128);
+/// let seed = Fixed(0x9e37_79b9u32);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<T>(pub T);
+
+impl<T> Deref for Fixed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Fixed<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Fixed<T> {
+    fn from(v: T) -> Self {
+        Fixed(v)
+    }
+}
+
+macro_rules! s11n_for_fixed {
+    ($($t:ty)*) => ($(
+        impl WireTypeTrait for Fixed<$t> {
+            const WIRE_TYPE: WireType = WireType::from_fixed_size(size_of::<$t>());
+            const BULK_COPY: bool = true;
+        }
+
+        impl Serialization for Fixed<$t> {
+            fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+                unsafe {
+                    let p = *ptr;
+                    let bytes = self.0.to_le_bytes();
+                    std::ptr::copy_nonoverlapping(bytes.as_ptr(), p, bytes.len());
+                    *ptr = p.add(bytes.len());
+                }
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                meta_data.size = size_of::<$t>()
+            }
+        }
+
+        impl<'de> DeSerialization<'de> for Fixed<$t> {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                unsafe {
+                    let p = *ptr;
+                    let end = p.add(size_of::<$t>());
+                    ctx.bounds_checker.check_end(end)?;
+                    let mut bytes = [0u8; size_of::<$t>()];
+                    std::ptr::copy_nonoverlapping(p, bytes.as_mut_ptr(), bytes.len());
+                    *ptr = end;
+                    Ok(Fixed(<$t>::from_le_bytes(bytes)))
+                }
+            }
+
+            fn padding() -> Option<Self> {
+                Some(Self::default())
+            }
+        }
+    )*)
+}
+
+s11n_for_fixed!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128);
@@ -28,6 +28,20 @@ impl SwapBytes for f64 {
     }
 }
 
+#[cfg(feature = "half")]
+impl SwapBytes for half::f16 {
+    fn swap_bytes(&self) -> Self {
+        Self::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+#[cfg(feature = "half")]
+impl SwapBytes for half::bf16 {
+    fn swap_bytes(&self) -> Self {
+        Self::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
 trait FloatWireType {
     const WIRE_TYPE: WireType;
 }
@@ -40,10 +54,23 @@ impl FloatWireType for f64 {
     const WIRE_TYPE: WireType = WireType::Bits64;
 }
 
+// half precision floats take half of the space of `f32`, which is enough for data like colors or
+// animation curves
+#[cfg(feature = "half")]
+impl FloatWireType for half::f16 {
+    const WIRE_TYPE: WireType = WireType::Bits16;
+}
+
+#[cfg(feature = "half")]
+impl FloatWireType for half::bf16 {
+    const WIRE_TYPE: WireType = WireType::Bits16;
+}
+
 macro_rules! s11n_for_floating {
     ($($t:ty)*) => ($(
         impl WireTypeTrait for $t {
             const WIRE_TYPE: WireType = <Self as FloatWireType>::WIRE_TYPE;
+            const BULK_COPY: bool = true;
         }
         impl Serialization for $t {
             fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
@@ -65,14 +92,14 @@ macro_rules! s11n_for_floating {
                 meta_data.size = size
             }
         }
-        impl DeSerialization for $t {
-            fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+        impl<'de> DeSerialization<'de> for $t {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
                 unsafe {
                     let p = *ptr;
                     let mut value = MaybeUninit::<Self>::uninit();
                     let dst : *mut u8 = value.as_mut_ptr() as *mut u8;
                     let size = std::mem::size_of::<$t>();
-                    ctx.bounds_checker.check_bounds(p)?;
+                    ctx.bounds_checker.check_end(p.add(size))?;
                     std::ptr::copy_nonoverlapping(p, dst, size);
                     let mut value = value.assume_init();
                     if cfg!(target_endian = "big") {
@@ -82,8 +109,42 @@ macro_rules! s11n_for_floating {
                     return Result::Ok(value);
                 }
             }
+
+            fn padding() -> Option<Self> {
+                Some(Self::default())
+            }
         }
     )*)
 }
 
 s11n_for_floating!(f32 f64);
+#[cfg(feature = "half")]
+s11n_for_floating!(half::f16 half::bf16);
+
+/// Encode a block of floats, which is the layout of most math types, e.g. vectors and matrices.
+#[cfg(any(feature = "glam", feature = "mint"))]
+pub(crate) fn encode_floats<F: Serialization>(
+    floats: &[F],
+    ptr: &mut *mut u8,
+    meta_data: &mut Metadata,
+) {
+    for float in floats {
+        float.encode(ptr, meta_data);
+    }
+}
+
+/// Decode a block of floats encoded by `encode_floats`.
+#[cfg(any(feature = "glam", feature = "mint"))]
+pub(crate) fn decode_floats<'de, F, const N: usize>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+) -> Result<[F; N], DecodeError>
+where
+    F: DeSerialization<'de> + Copy + Default,
+{
+    let mut floats = [F::default(); N];
+    for float in floats.iter_mut() {
+        *float = F::decode(ptr, ctx)?;
+    }
+    Ok(floats)
+}
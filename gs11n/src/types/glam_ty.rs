@@ -0,0 +1,62 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::floating::{decode_floats, encode_floats};
+use crate::meta_data::Metadata;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use glam::{
+    DMat2, DMat3, DMat4, DQuat, DVec2, DVec3, DVec4, Mat2, Mat3, Mat3A, Mat4, Quat, Vec2, Vec3,
+    Vec3A, Vec4,
+};
+use std::mem::size_of;
+
+// math types are encoded as blocks of floats, a block is bulk copied if the type has no padding,
+// e.g. `Vec3A` is aligned to 16 bytes, so it is encoded one by one
+macro_rules! s11n_for_glam {
+    ($($t:ty, [$f:ty; $n:literal], $v:ident => $to:expr, $a:ident => $from:expr;)*) => ($(
+        impl WireTypeTrait for $t {
+            const WIRE_TYPE: WireType = WireType::from_fixed_size(size_of::<[$f; $n]>());
+            const BULK_COPY: bool = size_of::<$t>() == size_of::<[$f; $n]>();
+        }
+
+        impl Serialization for $t {
+            fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+                let $v = self;
+                encode_floats(&$to, ptr, meta_data);
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                meta_data.size = size_of::<[$f; $n]>();
+            }
+        }
+
+        impl<'de> DeSerialization<'de> for $t {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                let $a: [$f; $n] = decode_floats(ptr, ctx)?;
+                Ok($from)
+            }
+
+            fn padding() -> Option<Self> {
+                Some(Self::default())
+            }
+        }
+    )*)
+}
+
+s11n_for_glam! {
+    Vec2, [f32; 2], v => v.to_array(), a => Vec2::from_array(a);
+    Vec3, [f32; 3], v => v.to_array(), a => Vec3::from_array(a);
+    Vec3A, [f32; 3], v => v.to_array(), a => Vec3A::from_array(a);
+    Vec4, [f32; 4], v => v.to_array(), a => Vec4::from_array(a);
+    Quat, [f32; 4], q => q.to_array(), a => Quat::from_array(a);
+    Mat2, [f32; 4], m => m.to_cols_array(), a => Mat2::from_cols_array(&a);
+    Mat3, [f32; 9], m => m.to_cols_array(), a => Mat3::from_cols_array(&a);
+    Mat3A, [f32; 9], m => m.to_cols_array(), a => Mat3A::from_cols_array(&a);
+    Mat4, [f32; 16], m => m.to_cols_array(), a => Mat4::from_cols_array(&a);
+    DVec2, [f64; 2], v => v.to_array(), a => DVec2::from_array(a);
+    DVec3, [f64; 3], v => v.to_array(), a => DVec3::from_array(a);
+    DVec4, [f64; 4], v => v.to_array(), a => DVec4::from_array(a);
+    DQuat, [f64; 4], q => q.to_array(), a => DQuat::from_array(a);
+    DMat2, [f64; 4], m => m.to_cols_array(), a => DMat2::from_cols_array(&a);
+    DMat3, [f64; 9], m => m.to_cols_array(), a => DMat3::from_cols_array(&a);
+    DMat4, [f64; 16], m => m.to_cols_array(), a => DMat4::from_cols_array(&a);
+}
@@ -0,0 +1,83 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::map::{decode_entries, encode_entries, record_entries};
+use crate::meta_data::Metadata;
+use crate::types::slice::{decode_elements, encode_elements, record_elements};
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use indexmap::{IndexMap, IndexSet};
+use std::hash::{BuildHasher, Hash};
+
+// entries are encoded in the order of the map, and inserted in the same order when decoding
+impl<K, V, S> WireTypeTrait for IndexMap<K, V, S> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<K, V, S> Serialization for IndexMap<K, V, S>
+where
+    K: Serialization,
+    V: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_entries(self.len(), self.iter(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_entries(self.len(), self.iter(), meta_data)
+    }
+}
+
+impl<'de, K, V, S> DeSerialization<'de> for IndexMap<K, V, S>
+where
+    K: DeSerialization<'de> + std::cmp::Eq + Hash,
+    V: DeSerialization<'de>,
+    S: Default + BuildHasher,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let mut map = Self::default();
+        decode_entries(ptr, ctx, |key, value| {
+            map.insert(key, value);
+            Ok(())
+        })?;
+        Ok(map)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+impl<T, S> WireTypeTrait for IndexSet<T, S> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T, S> Serialization for IndexSet<T, S>
+where
+    T: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_elements(self.len(), self.iter(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_elements(self.len(), self.iter(), meta_data)
+    }
+}
+
+impl<'de, T, S> DeSerialization<'de> for IndexSet<T, S>
+where
+    T: DeSerialization<'de> + std::cmp::Eq + Hash,
+    S: Default + BuildHasher,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let mut set = Self::default();
+        decode_elements(ptr, ctx, |element| {
+            set.insert(element);
+            Ok(())
+        })?;
+        Ok(set)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
@@ -1,4 +1,5 @@
-use crate::decoder::{DecodeContext, DecodeError};
+use crate::decoder::{decode_nested, DecodeContext, DecodeError};
+use crate::encoder::{encode_nested, size_of_nested};
 use crate::meta_data::Metadata;
 use crate::serialization::wire_type::WireType;
 use crate::unsigned::EncodeSize;
@@ -6,6 +7,66 @@ use crate::{DeSerialization, Serialization, WireTypeTrait};
 use std::collections::{BTreeMap, HashMap};
 use std::hash::{BuildHasher, Hash};
 
+/// Encode a length-prefixed sequence of entries, shared by all the map types.
+pub(crate) fn encode_entries<'a, K, V>(
+    len: usize,
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+    ptr: &mut *mut u8,
+    meta_data: &mut Metadata,
+) where
+    K: Serialization + 'a,
+    V: Serialization + 'a,
+{
+    // encode length
+    len.encode_raw(ptr);
+    let mut i = 0usize;
+    for (key, value) in entries {
+        encode_nested(key, ptr, meta_data.get(i));
+        encode_nested(value, ptr, meta_data.get(i + 1));
+        i += 2;
+    }
+}
+
+/// Record the size of a length-prefixed sequence of entries, shared by all the map types.
+pub(crate) fn record_entries<'a, K, V>(
+    len: usize,
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+    meta_data: &mut Metadata,
+) where
+    K: Serialization + 'a,
+    V: Serialization + 'a,
+{
+    let mut size = 0;
+    let mut i = 0usize;
+    for (key, value) in entries {
+        key.record(meta_data.get(i));
+        value.record(meta_data.get(i + 1));
+        size += size_of_nested::<K>(meta_data.get(i));
+        size += size_of_nested::<V>(meta_data.get(i + 1));
+        i += 2;
+    }
+    meta_data.size = size + len.varint_size();
+}
+
+/// Decode a length-prefixed sequence of entries, each entry is passed to `insert`.
+pub(crate) fn decode_entries<'de, K, V>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+    mut insert: impl FnMut(K, V) -> Result<(), DecodeError>,
+) -> Result<(), DecodeError>
+where
+    K: DeSerialization<'de>,
+    V: DeSerialization<'de>,
+{
+    let len = usize::decode(ptr, ctx)?;
+    for _ in 0..len {
+        let key = decode_nested(ptr, ctx)?;
+        let value = decode_nested(ptr, ctx)?;
+        insert(key, value)?;
+    }
+    Ok(())
+}
+
 impl<K, V, S> WireTypeTrait for HashMap<K, V, S> {
     const WIRE_TYPE: WireType = WireType::LengthDelimited;
 }
@@ -16,48 +77,32 @@ where
     V: Serialization,
 {
     fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
-        // encode length
-        self.len().encode_raw(ptr);
-        let mut i = 0usize;
-        for (key, value) in self {
-            key.encode(ptr, meta_data.get(i));
-            value.encode(ptr, meta_data.get(i + 1));
-            i += 2;
-        }
+        encode_entries(self.len(), self.iter(), ptr, meta_data)
     }
 
     fn record(&self, meta_data: &mut Metadata) {
-        let mut size = 0;
-        let mut i = 0usize;
-        for (key, value) in self {
-            key.record(meta_data.get(i));
-            value.record(meta_data.get(i + 1));
-            size += meta_data.get(i).size;
-            size += meta_data.get(i + 1).size;
-            i += 2;
-        }
-        meta_data.size = size + self.len().varint_size();
+        record_entries(self.len(), self.iter(), meta_data)
     }
 }
 
-impl<K, V, S> DeSerialization for HashMap<K, V, S>
+impl<'de, K, V, S> DeSerialization<'de> for HashMap<K, V, S>
 where
-    K: DeSerialization + std::cmp::Eq + Hash,
-    V: DeSerialization,
+    K: DeSerialization<'de> + std::cmp::Eq + Hash,
+    V: DeSerialization<'de>,
     S: Default + BuildHasher,
 {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         let mut map = Self::default();
-        let len = usize::decode(ptr, ctx)?;
-
-        for _ in 0..len {
-            let key = K::decode(ptr, ctx)?;
-            let value = V::decode(ptr, ctx)?;
+        decode_entries(ptr, ctx, |key, value| {
             map.insert(key, value);
-        }
-
+            Ok(())
+        })?;
         Ok(map)
     }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
 }
 
 impl<K, V> WireTypeTrait for BTreeMap<K, V> {
@@ -70,45 +115,29 @@ where
     V: Serialization,
 {
     fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
-        // encode length
-        self.len().encode_raw(ptr);
-        let mut i = 0usize;
-        for (key, value) in self {
-            key.encode(ptr, meta_data.get(i));
-            value.encode(ptr, meta_data.get(i + 1));
-            i += 2;
-        }
+        encode_entries(self.len(), self.iter(), ptr, meta_data)
     }
 
     fn record(&self, meta_data: &mut Metadata) {
-        let mut size = 0;
-        let mut i = 0usize;
-        for (key, value) in self {
-            key.record(meta_data.get(i));
-            value.record(meta_data.get(i + 1));
-            size += meta_data.get(i).size;
-            size += meta_data.get(i + 1).size;
-            i += 2;
-        }
-        meta_data.size = size + self.len().varint_size();
+        record_entries(self.len(), self.iter(), meta_data)
     }
 }
 
-impl<K, V> DeSerialization for BTreeMap<K, V>
+impl<'de, K, V> DeSerialization<'de> for BTreeMap<K, V>
 where
-    K: DeSerialization + std::cmp::Ord,
-    V: DeSerialization,
+    K: DeSerialization<'de> + std::cmp::Ord,
+    V: DeSerialization<'de>,
 {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         let mut map = Self::default();
-        let len = usize::decode(ptr, ctx)?;
-
-        for _ in 0..len {
-            let key = K::decode(ptr, ctx)?;
-            let value = V::decode(ptr, ctx)?;
+        decode_entries(ptr, ctx, |key, value| {
             map.insert(key, value);
-        }
-
+            Ok(())
+        })?;
         Ok(map)
     }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
 }
@@ -0,0 +1,61 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::floating::{decode_floats, encode_floats};
+use crate::meta_data::Metadata;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use mint::{
+    ColumnMatrix2, ColumnMatrix3, ColumnMatrix4, Point2, Point3, Quaternion, RowMatrix2,
+    RowMatrix3, RowMatrix4, Vector2, Vector3, Vector4,
+};
+use std::mem::size_of;
+
+// mint types are plain `#[repr(C)]` structs, so they are encoded as blocks of floats in the order
+// of their fields, just like the array they convert to
+macro_rules! s11n_for_mint {
+    ($($t:ident $n:literal)*) => ($(
+        s11n_for_mint!(@float $t $n f32);
+        s11n_for_mint!(@float $t $n f64);
+    )*);
+    (@float $t:ident $n:literal $f:ty) => {
+        impl WireTypeTrait for $t<$f> {
+            const WIRE_TYPE: WireType = WireType::from_fixed_size(size_of::<[$f; $n]>());
+            const BULK_COPY: bool = size_of::<$t<$f>>() == size_of::<[$f; $n]>();
+        }
+
+        impl Serialization for $t<$f> {
+            fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+                encode_floats(&<[$f; $n]>::from(*self), ptr, meta_data);
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                meta_data.size = size_of::<[$f; $n]>();
+            }
+        }
+
+        impl<'de> DeSerialization<'de> for $t<$f> {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                let floats: [$f; $n] = decode_floats(ptr, ctx)?;
+                Ok(floats.into())
+            }
+
+            fn padding() -> Option<Self> {
+                Some([0.0; $n].into())
+            }
+        }
+    };
+}
+
+s11n_for_mint! {
+    Vector2 2
+    Vector3 3
+    Vector4 4
+    Point2 2
+    Point3 3
+    Quaternion 4
+    ColumnMatrix2 4
+    ColumnMatrix3 9
+    ColumnMatrix4 16
+    RowMatrix2 4
+    RowMatrix3 9
+    RowMatrix4 16
+}
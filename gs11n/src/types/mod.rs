@@ -1,9 +1,36 @@
+#[cfg(feature = "arrayvec")]
+pub mod arrayvec_ty;
+pub mod bit_vec;
+pub mod bool;
 pub mod box_ty;
+pub mod cell;
 pub mod char;
+pub mod collection;
+pub mod cow;
+pub mod fixed;
 pub mod floating;
+#[cfg(feature = "glam")]
+pub mod glam_ty;
+#[cfg(feature = "indexmap")]
+pub mod indexmap_ty;
 pub mod map;
+#[cfg(feature = "mint")]
+pub mod mint_ty;
+pub mod net;
+pub mod non_zero;
 pub mod option;
+pub mod path;
+pub mod pointer;
+pub mod quantize;
+pub mod range;
+pub mod result;
 pub mod signed;
 pub mod slice;
+#[cfg(feature = "smallvec")]
+pub mod smallvec_ty;
 pub mod string;
+pub mod time;
+pub mod tuple;
+pub mod unit;
 pub mod unsigned;
+pub mod wrapper;
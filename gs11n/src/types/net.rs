@@ -0,0 +1,222 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::fixed::Fixed;
+use crate::meta_data::Metadata;
+use crate::unsigned::EncodeSize;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+// Addresses are encoded as their octets in network order, ports are fixed 2 bytes in little
+// endian like `Fixed<u16>`, `IpAddr` and `SocketAddr` are prefixed by a tag byte, 4 or 6.
+const V4_TAG: u8 = 4;
+const V6_TAG: u8 = 6;
+const PORT_SIZE: usize = 2;
+
+fn encode_octets(octets: &[u8], ptr: &mut *mut u8) {
+    unsafe {
+        let p = *ptr;
+        std::ptr::copy_nonoverlapping(octets.as_ptr(), p, octets.len());
+        *ptr = p.add(octets.len());
+    }
+}
+
+fn decode_octets<const N: usize>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext,
+) -> Result<[u8; N], DecodeError> {
+    unsafe {
+        let p = *ptr;
+        let end = p.add(N);
+        ctx.bounds_checker.check_end(end)?;
+        let mut octets = [0u8; N];
+        std::ptr::copy_nonoverlapping(p, octets.as_mut_ptr(), N);
+        *ptr = end;
+        Ok(octets)
+    }
+}
+
+impl WireTypeTrait for Ipv4Addr {
+    const WIRE_TYPE: WireType = WireType::Bits32;
+}
+
+impl Serialization for Ipv4Addr {
+    fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+        encode_octets(&self.octets(), ptr);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = 4;
+    }
+}
+
+impl<'de> DeSerialization<'de> for Ipv4Addr {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        Ok(decode_octets::<4>(ptr, ctx)?.into())
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Ipv4Addr::UNSPECIFIED)
+    }
+}
+
+impl WireTypeTrait for Ipv6Addr {
+    const WIRE_TYPE: WireType = WireType::Bits128;
+}
+
+impl Serialization for Ipv6Addr {
+    fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+        encode_octets(&self.octets(), ptr);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = 16;
+    }
+}
+
+impl<'de> DeSerialization<'de> for Ipv6Addr {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        Ok(decode_octets::<16>(ptr, ctx)?.into())
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Ipv6Addr::UNSPECIFIED)
+    }
+}
+
+impl WireTypeTrait for IpAddr {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for IpAddr {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        match self {
+            IpAddr::V4(ip) => {
+                V4_TAG.encode_raw(ptr);
+                ip.encode(ptr, meta_data);
+            }
+            IpAddr::V6(ip) => {
+                V6_TAG.encode_raw(ptr);
+                ip.encode(ptr, meta_data);
+            }
+        }
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = 1 + if self.is_ipv4() { 4 } else { 16 };
+    }
+}
+
+impl<'de> DeSerialization<'de> for IpAddr {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        match u8::decode(ptr, ctx)? {
+            V4_TAG => Ok(IpAddr::V4(Ipv4Addr::decode(ptr, ctx)?)),
+            V6_TAG => Ok(IpAddr::V6(Ipv6Addr::decode(ptr, ctx)?)),
+            _ => Err(DecodeError::InvalidType),
+        }
+    }
+
+    fn padding() -> Option<Self> {
+        Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+    }
+}
+
+impl WireTypeTrait for SocketAddrV4 {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for SocketAddrV4 {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        self.ip().encode(ptr, meta_data);
+        Fixed(self.port()).encode(ptr, meta_data);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = 4 + PORT_SIZE;
+    }
+}
+
+impl<'de> DeSerialization<'de> for SocketAddrV4 {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let ip = Ipv4Addr::decode(ptr, ctx)?;
+        let port = Fixed::<u16>::decode(ptr, ctx)?;
+        Ok(SocketAddrV4::new(ip, *port))
+    }
+
+    fn padding() -> Option<Self> {
+        Some(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))
+    }
+}
+
+// flow info and scope id are usually 0, so they are varint
+impl WireTypeTrait for SocketAddrV6 {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for SocketAddrV6 {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        self.ip().encode(ptr, meta_data);
+        Fixed(self.port()).encode(ptr, meta_data);
+        self.flowinfo().encode_raw(ptr);
+        self.scope_id().encode_raw(ptr);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size =
+            16 + PORT_SIZE + self.flowinfo().varint_size() + self.scope_id().varint_size();
+    }
+}
+
+impl<'de> DeSerialization<'de> for SocketAddrV6 {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let ip = Ipv6Addr::decode(ptr, ctx)?;
+        let port = Fixed::<u16>::decode(ptr, ctx)?;
+        let flowinfo = u32::decode(ptr, ctx)?;
+        let scope_id = u32::decode(ptr, ctx)?;
+        Ok(SocketAddrV6::new(ip, *port, flowinfo, scope_id))
+    }
+
+    fn padding() -> Option<Self> {
+        Some(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0))
+    }
+}
+
+impl WireTypeTrait for SocketAddr {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for SocketAddr {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        match self {
+            SocketAddr::V4(addr) => {
+                V4_TAG.encode_raw(ptr);
+                addr.encode(ptr, meta_data);
+            }
+            SocketAddr::V6(addr) => {
+                V6_TAG.encode_raw(ptr);
+                addr.encode(ptr, meta_data);
+            }
+        }
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        match self {
+            SocketAddr::V4(addr) => addr.record(meta_data),
+            SocketAddr::V6(addr) => addr.record(meta_data),
+        }
+        meta_data.size += 1;
+    }
+}
+
+impl<'de> DeSerialization<'de> for SocketAddr {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        match u8::decode(ptr, ctx)? {
+            V4_TAG => Ok(SocketAddr::V4(SocketAddrV4::decode(ptr, ctx)?)),
+            V6_TAG => Ok(SocketAddr::V6(SocketAddrV6::decode(ptr, ctx)?)),
+            _ => Err(DecodeError::InvalidType),
+        }
+    }
+
+    fn padding() -> Option<Self> {
+        Some(SocketAddr::V4(SocketAddrV4::padding()?))
+    }
+}
@@ -0,0 +1,49 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+// non zero integers are encoded as the integers they wrap
+macro_rules! s11n_for_non_zero {
+    ($($t:ty, $int:ty)*) => ($(
+        impl WireTypeTrait for $t {
+            const WIRE_TYPE: WireType = <$int>::WIRE_TYPE;
+        }
+
+        impl Serialization for $t {
+            fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+                self.get().encode(ptr, meta_data)
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                self.get().record(meta_data)
+            }
+        }
+
+        impl<'de> DeSerialization<'de> for $t {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                let v = <$int>::decode(ptr, ctx)?;
+                <$t>::new(v).ok_or(DecodeError::InvalidNonZero)
+            }
+        }
+    )*)
+}
+
+s11n_for_non_zero!(
+    NonZeroU8, u8
+    NonZeroU16, u16
+    NonZeroU32, u32
+    NonZeroU64, u64
+    NonZeroU128, u128
+    NonZeroUsize, usize
+    NonZeroI8, i8
+    NonZeroI16, i16
+    NonZeroI32, i32
+    NonZeroI64, i64
+    NonZeroI128, i128
+    NonZeroIsize, isize
+);
@@ -13,8 +13,15 @@ impl<S: Serialization> Serialization for Option<S> {
         match self {
             Some(v) => {
                 let size = meta_data.get(0).size;
-                size.encode(ptr, meta_data);
-                v.encode(ptr, meta_data.get(0))
+                if size == 0 {
+                    // A zero sized value can not be told from None, so pad it with one byte.
+                    1usize.encode(ptr, meta_data);
+                    v.encode(ptr, meta_data.get(0));
+                    0u8.encode(ptr, meta_data);
+                } else {
+                    size.encode(ptr, meta_data);
+                    v.encode(ptr, meta_data.get(0))
+                }
             }
             None => {
                 let size = 0usize;
@@ -27,7 +34,7 @@ impl<S: Serialization> Serialization for Option<S> {
         meta_data.size = match self {
             Some(v) => {
                 v.record(meta_data.get(0));
-                let element_size = meta_data.get(0).size;
+                let element_size = meta_data.get(0).size.max(1);
                 element_size.varint_size() + element_size
             }
             None => 1,
@@ -35,14 +42,21 @@ impl<S: Serialization> Serialization for Option<S> {
     }
 }
 
-impl<S: DeSerialization> DeSerialization for Option<S> {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+impl<'de, S: DeSerialization<'de>> DeSerialization<'de> for Option<S> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         let size = usize::decode(ptr, ctx)?;
         if size == 0 {
             Ok(None)
         } else {
+            let end = ctx.bounds_checker.end_of(*ptr, size)?;
             let v = S::decode(ptr, ctx)?;
+            // skip the padding of zero sized values
+            *ptr = end;
             Ok(Some(v))
         }
     }
+
+    fn padding() -> Option<Self> {
+        Some(None)
+    }
 }
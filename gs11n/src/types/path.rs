@@ -0,0 +1,53 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::encoder::{report_encode_error, EncodeError};
+use crate::meta_data::Metadata;
+use crate::string::{encode_str, record_str};
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+
+/// Paths and OS strings are encoded as strings, so that the data is portable between platforms.
+/// Only valid UTF-8 can be encoded, other values are reported as `EncodeError::InvalidUtf8` when
+/// they are recorded, and recorded and encoded as empty strings.
+fn to_utf8(value: &OsStr) -> &str {
+    value.to_str().unwrap_or_default()
+}
+
+macro_rules! s11n_for_os_str {
+    ($($t:ty, $v:ident => $os_str:expr;)*) => ($(
+        impl WireTypeTrait for $t {
+            const WIRE_TYPE: WireType = WireType::LengthDelimited;
+        }
+
+        impl Serialization for $t {
+            fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+                let $v = self;
+                encode_str(to_utf8($os_str), ptr);
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                let $v = self;
+                if $os_str.to_str().is_none() {
+                    report_encode_error(EncodeError::InvalidUtf8);
+                }
+                record_str(to_utf8($os_str), meta_data);
+            }
+        }
+
+        impl<'de> DeSerialization<'de> for $t {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                Ok(<&str>::decode(ptr, ctx)?.into())
+            }
+
+            fn padding() -> Option<Self> {
+                Some(Self::default())
+            }
+        }
+    )*)
+}
+
+s11n_for_os_str! {
+    PathBuf, path => path.as_os_str();
+    OsString, string => string.as_os_str();
+}
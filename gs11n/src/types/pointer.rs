@@ -0,0 +1,172 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::encoder::{report_encode_error, EncodeError};
+use crate::meta_data::Metadata;
+use crate::serialization::shared::{
+    decode_tag, decode_value, encode_reference, is_shared_mode, is_tracked, record_reference,
+    Reference, ReferenceTable,
+};
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::rc::Rc;
+use std::sync::Arc;
+
+// references and shared pointers are encoded exactly like the value they point to, unless shared
+// references mode is on, check shared.rs for details
+
+impl<T: WireTypeTrait> WireTypeTrait for &T {
+    const WIRE_TYPE: WireType = T::WIRE_TYPE;
+    const SELF_DELIMITED: bool = T::SELF_DELIMITED;
+}
+
+impl<T: Serialization> Serialization for &T {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        T::encode(self, ptr, meta_data);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        T::record(self, meta_data);
+    }
+}
+
+macro_rules! s11n_for_pointer {
+    ($($p:ident $weak:ty, $shared:ident)*) => ($(
+        /// Decode the value of an allocation met the first time, the allocation is added to the
+        /// table before decoding the value, so that the value can refer to it by `Weak`.
+        fn $shared<'de, T: DeSerialization<'de> + 'static>(
+            ptr: &mut *const u8,
+            ctx: &DecodeContext<'de>,
+            references: &ReferenceTable,
+        ) -> Result<$p<T>, DecodeError> {
+            let uninit = $p::new(UnsafeCell::new(MaybeUninit::<T>::uninit()));
+            let id = references.insert(uninit.clone());
+            let value = decode_value::<T>(ptr, ctx)?;
+            // `UnsafeCell<MaybeUninit<T>>` has the same layout as `T`
+            let pointer = unsafe {
+                (*uninit.get()).write(value);
+                $p::from_raw($p::into_raw(uninit) as *const T)
+            };
+            references.initialize(id, pointer.clone());
+            Ok(pointer)
+        }
+
+        impl<T: WireTypeTrait> WireTypeTrait for $p<T> {
+            const WIRE_TYPE: WireType = T::WIRE_TYPE;
+            const SELF_DELIMITED: bool = T::SELF_DELIMITED;
+        }
+
+        impl<T: Serialization> Serialization for $p<T> {
+            fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+                if is_tracked::<T>() {
+                    encode_reference(Some(&**self), ptr, meta_data);
+                } else {
+                    T::encode(self, ptr, meta_data);
+                }
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                if is_tracked::<T>() {
+                    record_reference(Some(&**self), meta_data);
+                } else {
+                    T::record(self, meta_data);
+                }
+            }
+        }
+
+        impl<'de, T: DeSerialization<'de> + 'static> DeSerialization<'de> for $p<T> {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                match &ctx.references {
+                    Some(references) if T::WIRE_TYPE == WireType::LengthDelimited => {
+                        match decode_tag(ptr, ctx)? {
+                            Reference::Null => Err(DecodeError::InvalidReference),
+                            Reference::New => $shared(ptr, ctx, references),
+                            Reference::Back(id) => references.with(id, |pointer| {
+                                if let Some(pointer) = pointer.downcast_ref::<$p<T>>() {
+                                    Ok(pointer.clone())
+                                } else if pointer.is::<$p<UnsafeCell<MaybeUninit<T>>>>() {
+                                    Err(DecodeError::ReferenceCycle)
+                                } else {
+                                    Err(DecodeError::InvalidReference)
+                                }
+                            }),
+                        }
+                    }
+                    _ => {
+                        let t = T::decode(ptr, ctx)?;
+                        Ok($p::new(t))
+                    }
+                }
+            }
+
+            fn padding() -> Option<Self> {
+                T::padding().map($p::new)
+            }
+        }
+
+        // a `Weak` is always encoded as a reference, which is null if it can not be upgraded, it
+        // can only point to an allocation tracked by shared references mode
+        impl<T> WireTypeTrait for $weak {
+            const WIRE_TYPE: WireType = WireType::LengthDelimited;
+        }
+
+        impl<T: Serialization> Serialization for $weak {
+            fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+                let target = self.upgrade().filter(|_| is_tracked::<T>());
+                encode_reference(target.as_deref(), ptr, meta_data);
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                let target = self.upgrade();
+                if target.is_some() && !is_tracked::<T>() {
+                    report_encode_error(if is_shared_mode() {
+                        EncodeError::UntrackedWeak
+                    } else {
+                        EncodeError::UnsharedWeak
+                    });
+                }
+                let target = target.filter(|_| is_tracked::<T>());
+                record_reference(target.as_deref(), meta_data);
+            }
+        }
+
+        impl<'de, T: DeSerialization<'de> + 'static> DeSerialization<'de> for $weak {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                let reference = decode_tag(ptr, ctx)?;
+                let references = match (&ctx.references, reference) {
+                    (_, Reference::Null) => return Ok(<$weak>::new()),
+                    (Some(references), _) if T::WIRE_TYPE == WireType::LengthDelimited => {
+                        references
+                    }
+                    _ => return Err(DecodeError::InvalidReference),
+                };
+                match reference {
+                    Reference::Back(id) => references.with(id, |pointer| {
+                        if let Some(pointer) = pointer.downcast_ref::<$p<T>>() {
+                            Ok($p::downgrade(pointer))
+                        } else if let Some(uninit) =
+                            pointer.downcast_ref::<$p<UnsafeCell<MaybeUninit<T>>>>()
+                        {
+                            // the value is still being decoded, and is written before the
+                            // decoding returns, so the `Weak` is valid once it can be used
+                            let weak = $p::downgrade(uninit);
+                            Ok(unsafe { <$weak>::from_raw(weak.into_raw() as *const T) })
+                        } else {
+                            Err(DecodeError::InvalidReference)
+                        }
+                    }),
+                    _ => Ok($p::downgrade(&$shared::<T>(ptr, ctx, references)?)),
+                }
+            }
+
+            fn padding() -> Option<Self> {
+                Some(<$weak>::new())
+            }
+        }
+    )*)
+}
+
+s11n_for_pointer!(
+    Rc std::rc::Weak<T>, decode_shared_rc
+    Arc std::sync::Weak<T>, decode_shared_arc
+);
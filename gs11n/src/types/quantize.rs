@@ -0,0 +1,65 @@
+/// Map a float in `[min, max]` to an integer of `bits` bits, used by fields with attribute
+/// `#[serialized(id, quantize(min = .., max = .., bits = ..))]`, the integer is encoded as a
+/// varint instead of the float.
+///
+/// Values out of the range are clamped, `NaN` is mapped to `min`. For values in the range, the
+/// error after decoding is at most half a step, that is `(max - min) / (2^bits - 1) / 2`, plus the
+/// rounding error of the float type itself.
+/// ```
+/// use gs11n::quantize::Quantizer;
+///
+/// let quantizer = Quantizer::new(-512.0, 512.0, 16);
+/// let decoded = quantizer.dequantize(quantizer.quantize(100.0));
+/// assert!((decoded - 100.0).abs() <= quantizer.max_error());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quantizer {
+    min: f64,
+    max: f64,
+    bits: u32,
+}
+
+impl Quantizer {
+    /// # Panics
+    /// Panics if `min` is not less than `max`, or `bits` is not in `1..=32`.
+    pub fn new(min: f64, max: f64, bits: u32) -> Self {
+        assert!(min < max, "gs11n: quantize range must have min < max");
+        Self::new_unchecked(min, max, bits)
+    }
+
+    /// Like `new`, but `min < max` is not checked, so that it can be used in constants, the derived
+    /// code checks it by itself.
+    ///
+    /// # Panics
+    /// Panics if `bits` is not in `1..=32`.
+    pub const fn new_unchecked(min: f64, max: f64, bits: u32) -> Self {
+        assert!(
+            bits >= 1 && bits <= 32,
+            "gs11n: quantize bits must be in 1..=32"
+        );
+        Quantizer { min, max, bits }
+    }
+
+    fn steps(&self) -> f64 {
+        ((1u64 << self.bits) - 1) as f64
+    }
+
+    pub fn quantize(&self, value: f64) -> u32 {
+        let value = value.clamp(self.min, self.max);
+        // `as` saturates, and maps `NaN` to 0
+        ((value - self.min) / (self.max - self.min) * self.steps()).round() as u32
+    }
+
+    /// Integers beyond `2^bits - 1` are mapped to `max`.
+    pub fn dequantize(&self, quantized: u32) -> f64 {
+        let steps = self.steps();
+        let quantized = (quantized as f64).min(steps);
+        self.min + quantized * (self.max - self.min) / steps
+    }
+
+    /// The max error of a value in the range after quantized, ignoring the rounding error of the
+    /// float type.
+    pub fn max_error(&self) -> f64 {
+        (self.max - self.min) / self.steps() / 2.0
+    }
+}
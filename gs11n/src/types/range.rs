@@ -0,0 +1,108 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo};
+
+// Ranges are encoded like `#[compact]` structs, `Range` and `RangeInclusive` are encoded exactly
+// like a tuple `(start, end)`, `RangeFrom` and `RangeTo` are encoded exactly like their bound.
+
+impl<T: WireTypeTrait> WireTypeTrait for Range<T> {
+    const WIRE_TYPE: WireType = <(T, T)>::WIRE_TYPE;
+}
+
+impl<T: Serialization> Serialization for Range<T> {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        (&self.start, &self.end).encode(ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        (&self.start, &self.end).record(meta_data)
+    }
+}
+
+impl<'de, T: DeSerialization<'de>> DeSerialization<'de> for Range<T> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let (start, end) = <(T, T)>::decode(ptr, ctx)?;
+        Ok(start..end)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(T::padding()?..T::padding()?)
+    }
+}
+
+impl<T: WireTypeTrait> WireTypeTrait for RangeInclusive<T> {
+    const WIRE_TYPE: WireType = <(T, T)>::WIRE_TYPE;
+}
+
+impl<T: Serialization> Serialization for RangeInclusive<T> {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        (self.start(), self.end()).encode(ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        (self.start(), self.end()).record(meta_data)
+    }
+}
+
+impl<'de, T: DeSerialization<'de>> DeSerialization<'de> for RangeInclusive<T> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let (start, end) = <(T, T)>::decode(ptr, ctx)?;
+        Ok(start..=end)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(T::padding()?..=T::padding()?)
+    }
+}
+
+impl<T: WireTypeTrait> WireTypeTrait for RangeFrom<T> {
+    const WIRE_TYPE: WireType = T::WIRE_TYPE;
+    const SELF_DELIMITED: bool = T::SELF_DELIMITED;
+}
+
+impl<T: Serialization> Serialization for RangeFrom<T> {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        self.start.encode(ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        self.start.record(meta_data)
+    }
+}
+
+impl<'de, T: DeSerialization<'de>> DeSerialization<'de> for RangeFrom<T> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        Ok(T::decode(ptr, ctx)?..)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(T::padding()?..)
+    }
+}
+
+impl<T: WireTypeTrait> WireTypeTrait for RangeTo<T> {
+    const WIRE_TYPE: WireType = T::WIRE_TYPE;
+    const SELF_DELIMITED: bool = T::SELF_DELIMITED;
+}
+
+impl<T: Serialization> Serialization for RangeTo<T> {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        self.end.encode(ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        self.end.record(meta_data)
+    }
+}
+
+impl<'de, T: DeSerialization<'de>> DeSerialization<'de> for RangeTo<T> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        Ok(..T::decode(ptr, ctx)?)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(..T::padding()?)
+    }
+}
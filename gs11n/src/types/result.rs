@@ -0,0 +1,53 @@
+use crate::decoder::{decode_nested, DecodeContext, DecodeError};
+use crate::encoder::{encode_nested, size_of_nested};
+use crate::meta_data::Metadata;
+use crate::unsigned::EncodeSize;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+
+// A result is encoded like a derived enum, a variant id (`Ok` is 0 and `Err` is 1) followed by
+// the value of the variant.
+const OK_ID: usize = 0;
+const ERR_ID: usize = 1;
+
+impl<T, E> WireTypeTrait for Result<T, E> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T: Serialization, E: Serialization> Serialization for Result<T, E> {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        match self {
+            Ok(v) => {
+                OK_ID.encode_raw(ptr);
+                encode_nested(v, ptr, meta_data.get(0));
+            }
+            Err(e) => {
+                ERR_ID.encode_raw(ptr);
+                encode_nested(e, ptr, meta_data.get(0));
+            }
+        }
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = match self {
+            Ok(v) => {
+                v.record(meta_data.get(0));
+                OK_ID.varint_size() + size_of_nested::<T>(meta_data.get(0))
+            }
+            Err(e) => {
+                e.record(meta_data.get(0));
+                ERR_ID.varint_size() + size_of_nested::<E>(meta_data.get(0))
+            }
+        };
+    }
+}
+
+impl<'de, T: DeSerialization<'de>, E: DeSerialization<'de>> DeSerialization<'de> for Result<T, E> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        match usize::decode(ptr, ctx)? {
+            OK_ID => Ok(Ok(decode_nested(ptr, ctx)?)),
+            ERR_ID => Ok(Err(decode_nested(ptr, ctx)?)),
+            _ => Err(DecodeError::InvalidType),
+        }
+    }
+}
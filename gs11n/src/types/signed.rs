@@ -39,7 +39,6 @@ impl_zigzag_for!(i16, u16);
 impl_zigzag_for!(i32, u32);
 impl_zigzag_for!(i64, u64);
 impl_zigzag_for!(isize, usize);
-#[cfg(has_i128)]
 impl_zigzag_for!(i128, u128);
 
 macro_rules! s11n_for_signed {
@@ -59,16 +58,18 @@ macro_rules! s11n_for_signed {
                 meta_data.size = size
             }
         }
-        impl DeSerialization for $t {
-            fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+        impl<'de> DeSerialization<'de> for $t {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
                 let zigzag = <Self as ZigZag>::UnsignedTy::decode(ptr, ctx)?;
                 let value = zigzag.unzigzag();
                 Result::Ok(value)
             }
+
+            fn padding() -> Option<Self> {
+                Some(Self::default())
+            }
         }
     )*)
 }
 
-s11n_for_signed!(isize i8 i16 i32 i64);
-#[cfg(has_i128)]
-s11n_for_signed!(i128);
+s11n_for_signed!(isize i8 i16 i32 i64 i128);
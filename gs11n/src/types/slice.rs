@@ -1,48 +1,176 @@
-use crate::decoder::{DecodeContext, DecodeError};
+use crate::decoder::{decode_nested, ArrayLengthPolicy, DecodeContext, DecodeError};
+use crate::encoder::{encode_nested, size_of_nested};
 use crate::meta_data::Metadata;
 use crate::unsigned::EncodeSize;
 use crate::wire_type::WireType;
 use crate::{DeSerialization, Serialization, WireTypeTrait};
-use std::mem::{size_of, MaybeUninit};
+use std::mem::{size_of, ManuallyDrop, MaybeUninit};
 
-fn encode_slice<S: Serialization>(value: &[S], ptr: &mut *mut u8, meta_data: &mut Metadata) {
-    // encode size first
-    value.len().encode_raw(ptr);
+/// Can a slice of `S` be copied as a whole, instead of encoding one by one.
+fn can_bulk_copy<S: WireTypeTrait>() -> bool {
+    S::BULK_COPY && (size_of::<S>() == 1 || !cfg!(target_endian = "big"))
+}
 
-    if (S::WIRE_TYPE.is_fixed_type() && S::WIRE_TYPE == WireType::Bits8)
-        || (S::WIRE_TYPE.is_fixed_type() && !cfg!(target_endian = "big"))
-    {
+/// Encode an element of a sequence, a bulk copied element is encoded as its memory.
+fn encode_element<S: Serialization>(element: &S, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+    if can_bulk_copy::<S>() {
         unsafe {
-            let mut p = *ptr;
-            std::ptr::copy_nonoverlapping(value.as_ptr(), p as *mut S, value.len());
-            p = ((p as *mut S).add(value.len())) as *mut u8;
-            *ptr = p;
+            let p = *ptr;
+            std::ptr::copy_nonoverlapping(element as *const S as *const u8, p, size_of::<S>());
+            *ptr = p.add(size_of::<S>());
         }
     } else {
-        for i in 0..value.len() {
-            value[i].encode(ptr, meta_data.get(i));
+        encode_nested(element, ptr, meta_data);
+    }
+}
+
+/// Decode an element of a sequence, shared by all the collection types.
+pub(crate) fn decode_element<'de, S: DeSerialization<'de>>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+) -> Result<S, DecodeError> {
+    if can_bulk_copy::<S>() {
+        unsafe {
+            let p = *ptr;
+            let end = p.add(size_of::<S>());
+            ctx.bounds_checker.check_end(end)?;
+            *ptr = end;
+            Ok((p as *const S).read_unaligned())
         }
+    } else {
+        decode_nested(ptr, ctx)
     }
 }
 
-fn record_slice<S: Serialization>(value: &[S], meta_data: &mut Metadata) {
-    let mut size = value.len().varint_size();
-    if S::WIRE_TYPE.is_fixed_type() {
-        size += value.len() * std::mem::size_of::<S>();
+/// Encode a length-prefixed sequence of elements one by one, shared by all the collection types.
+pub(crate) fn encode_elements<'a, S: Serialization + 'a>(
+    len: usize,
+    elements: impl Iterator<Item = &'a S>,
+    ptr: &mut *mut u8,
+    meta_data: &mut Metadata,
+) {
+    // encode size first
+    len.encode_raw(ptr);
+    if S::BULK_COPY || S::WIRE_TYPE.is_fixed_type() {
+        // the sizes of such elements are not recorded, so they need no metadata
+        let mut unused = Metadata::default();
+        for element in elements {
+            encode_element(element, ptr, &mut unused);
+        }
     } else {
-        for i in 0..value.len() {
-            value[i].record(meta_data.get(i));
-            size += meta_data.get(i).size;
+        for (i, element) in elements.enumerate() {
+            encode_element(element, ptr, meta_data.get(i));
+        }
+    }
+}
+
+/// Record the size of a length-prefixed sequence of elements, shared by all the collection types.
+pub(crate) fn record_elements<'a, S: Serialization + 'a>(
+    len: usize,
+    elements: impl Iterator<Item = &'a S>,
+    meta_data: &mut Metadata,
+) {
+    let mut size = len.varint_size();
+    if S::BULK_COPY {
+        size += len * size_of::<S>();
+    } else if S::WIRE_TYPE.is_fixed_type() {
+        size += len * S::WIRE_TYPE.fixed_size();
+    } else {
+        for (i, element) in elements.enumerate() {
+            element.record(meta_data.get(i));
+            size += size_of_nested::<S>(meta_data.get(i));
         }
     }
     meta_data.size = size;
 }
 
+/// Decode a length-prefixed sequence of elements one by one, each element is passed to `push`.
+pub(crate) fn decode_elements<'de, S: DeSerialization<'de>>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+    mut push: impl FnMut(S) -> Result<(), DecodeError>,
+) -> Result<(), DecodeError> {
+    let len = usize::decode(ptr, ctx)?;
+    for _ in 0..len {
+        push(decode_element(ptr, ctx)?)?;
+    }
+    Ok(())
+}
+
+/// Encode the count of elements followed by the memory of the whole slice.
+fn encode_memory<S>(value: &[S], ptr: &mut *mut u8) {
+    value.len().encode_raw(ptr);
+    unsafe {
+        let p = *ptr;
+        let size = std::mem::size_of_val(value);
+        std::ptr::copy_nonoverlapping(value.as_ptr() as *const u8, p, size);
+        *ptr = p.add(size);
+    }
+}
+
+pub(crate) fn encode_slice<S: Serialization>(
+    value: &[S],
+    ptr: &mut *mut u8,
+    meta_data: &mut Metadata,
+) {
+    if can_bulk_copy::<S>() {
+        encode_memory(value, ptr);
+    } else {
+        encode_elements(value.len(), value.iter(), ptr, meta_data);
+    }
+}
+
+/// The count of bytes from `ptr` to the bound of the encoded data.
+fn remaining(ptr: *const u8, ctx: &DecodeContext<'_>) -> usize {
+    (ctx.bounds_checker.get_bound() as usize).saturating_sub(ptr as usize)
+}
+
+/// The size of `counts` bulk copied elements starting at `start`, fails if they are out of the
+/// bounds, without computing a pointer out of the bounds.
+pub(crate) fn bulk_size<S>(
+    counts: usize,
+    start: *const u8,
+    ctx: &DecodeContext<'_>,
+) -> Result<usize, DecodeError> {
+    let size = counts
+        .checked_mul(size_of::<S>())
+        .ok_or(DecodeError::DecodeOutOfBounds)?;
+    if size > remaining(start, ctx) {
+        return Err(DecodeError::DecodeOutOfBounds);
+    }
+    Ok(size)
+}
+
+/// Borrow a length-prefixed bytes from the encoded data.
+pub(crate) fn decode_bytes<'de>(
+    ptr: &mut *const u8,
+    ctx: &DecodeContext<'de>,
+) -> Result<&'de [u8], DecodeError> {
+    let counts = usize::decode(ptr, ctx)?;
+    unsafe {
+        let start = *ptr;
+        let end = start.add(bulk_size::<u8>(counts, start, ctx)?);
+        *ptr = end;
+        // the encoded data lives as long as `'de`
+        Ok(std::slice::from_raw_parts(start, counts))
+    }
+}
+
+pub(crate) fn record_slice<S: Serialization>(value: &[S], meta_data: &mut Metadata) {
+    record_elements(value.len(), value.iter(), meta_data)
+}
+
 impl<S> WireTypeTrait for Vec<S> {
     const WIRE_TYPE: WireType = WireType::LengthDelimited;
 }
 
-impl<S> WireTypeTrait for &[S] {
+impl<S: WireTypeTrait> WireTypeTrait for &[S] {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+    // a slice of slices of bytes is not
+    const BYTES: bool = S::BYTES && size_of::<S>() == 1;
+}
+
+impl<S> WireTypeTrait for Box<[S]> {
     const WIRE_TYPE: WireType = WireType::LengthDelimited;
 }
 
@@ -52,11 +180,19 @@ impl<S, const N: usize> WireTypeTrait for [S; N] {
 
 impl<S: Serialization> Serialization for &[S] {
     fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
-        encode_slice(self, ptr, meta_data)
+        if Self::BYTES {
+            encode_memory(self, ptr)
+        } else {
+            encode_slice(self, ptr, meta_data)
+        }
     }
 
     fn record(&self, meta_data: &mut Metadata) {
-        record_slice(self, meta_data)
+        if Self::BYTES {
+            meta_data.size = self.len().varint_size() + self.len();
+        } else {
+            record_slice(self, meta_data)
+        }
     }
 }
 
@@ -80,58 +216,156 @@ impl<S: Serialization> Serialization for Vec<S> {
     }
 }
 
-impl<S: DeSerialization> DeSerialization for Vec<S> {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+impl<S: Serialization> Serialization for Box<[S]> {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_slice(self, ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_slice(self, meta_data)
+    }
+}
+
+impl<'de, S: DeSerialization<'de>> DeSerialization<'de> for Vec<S> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         let counts = usize::decode(ptr, ctx)?;
 
         let mut vec: Vec<S> = Vec::new();
 
-        if (S::WIRE_TYPE.is_fixed_type() && S::WIRE_TYPE == WireType::Bits8)
-            || (S::WIRE_TYPE.is_fixed_type() && !cfg!(target_endian = "big"))
-        {
+        if can_bulk_copy::<S>() {
             unsafe {
                 let start = *ptr;
-                let end = (start).add(counts * size_of::<S>());
-                ctx.bounds_checker.check_bounds(end.sub(1))?;
-                vec.resize_with(counts, || MaybeUninit::uninit().assume_init());
-                std::ptr::copy_nonoverlapping(start as *mut S, vec.as_mut_ptr(), counts);
+                let size = bulk_size::<S>(counts, start, ctx)?;
+                let end = start.add(size);
+                vec.reserve(counts);
+                std::ptr::copy_nonoverlapping(start, vec.as_mut_ptr() as *mut u8, size);
+                vec.set_len(counts);
                 *ptr = end;
                 Ok(vec)
             }
         } else {
-            vec.reserve(counts);
+            // at most a slot per remaining byte, so a corrupted count can not reserve too much memory
+            vec.reserve(counts.min(remaining(*ptr, ctx)));
             for _ in 0..counts {
-                let element = S::decode(ptr, ctx)?;
+                let element = decode_element(ptr, ctx)?;
                 vec.push(element);
             }
             Ok(vec)
         }
     }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
 }
 
-impl<S: DeSerialization, const N: usize> DeSerialization for [S; N] {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
-        let counts = usize::decode(ptr, ctx)?;
+/// An array which is being decoded, the decoded elements are dropped if decoding fails halfway.
+struct PartialArray<S, const N: usize> {
+    array: MaybeUninit<[S; N]>,
+    len: usize,
+}
+
+impl<S, const N: usize> PartialArray<S, N> {
+    fn new() -> Self {
+        PartialArray {
+            array: MaybeUninit::uninit(),
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, element: S) {
+        debug_assert!(self.len < N);
+        unsafe {
+            (self.array.as_mut_ptr() as *mut S)
+                .add(self.len)
+                .write(element);
+        }
+        self.len += 1;
+    }
+
+    fn finish(self) -> [S; N] {
+        assert_eq!(self.len, N);
+        // the elements are owned by the returned array now
+        let this = ManuallyDrop::new(self);
+        unsafe { this.array.as_ptr().read() }
+    }
+}
 
-        let mut array: [S; N] = unsafe { MaybeUninit::uninit().assume_init() };
+impl<S, const N: usize> Drop for PartialArray<S, N> {
+    fn drop(&mut self) {
+        unsafe {
+            let elements =
+                std::ptr::slice_from_raw_parts_mut(self.array.as_mut_ptr() as *mut S, self.len);
+            std::ptr::drop_in_place(elements);
+        }
+    }
+}
 
-        if (S::WIRE_TYPE.is_fixed_type() && S::WIRE_TYPE == WireType::Bits8)
-            || (S::WIRE_TYPE.is_fixed_type() && !cfg!(target_endian = "big"))
-        {
+impl<'de, S: DeSerialization<'de>, const N: usize> DeSerialization<'de> for [S; N] {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let counts = usize::decode(ptr, ctx)?;
+        match ctx.array_length_policy {
+            _ if counts == N => {}
+            ArrayLengthPolicy::Truncate if counts > N => {}
+            ArrayLengthPolicy::Pad => {}
+            _ => return Err(DecodeError::ArrayLengthMismatch),
+        }
+
+        let mut array = PartialArray::<S, N>::new();
+        if can_bulk_copy::<S>() {
             unsafe {
                 let start = *ptr;
-                let end = (start).add(counts * size_of::<S>());
-                ctx.bounds_checker.check_bounds(end.sub(1))?;
-                std::ptr::copy_nonoverlapping(start as *mut S, array.as_mut_ptr(), counts);
+                let size = bulk_size::<S>(counts, start, ctx)?;
+                let end = start.add(size);
+                let copied = counts.min(N);
+                std::ptr::copy_nonoverlapping(
+                    start,
+                    array.array.as_mut_ptr() as *mut u8,
+                    copied * size_of::<S>(),
+                );
+                array.len = copied;
                 *ptr = end;
-                Ok(array)
             }
         } else {
             for i in 0..counts {
-                let element = S::decode(ptr, ctx)?;
-                array[i] = element;
+                let element = decode_element::<S>(ptr, ctx)?;
+                if i < N {
+                    array.push(element);
+                }
             }
-            Ok(array)
         }
+        while array.len < N {
+            array.push(S::padding().ok_or(DecodeError::ArrayLengthMismatch)?);
+        }
+        Ok(array.finish())
+    }
+
+    fn padding() -> Option<Self> {
+        let mut array = PartialArray::<S, N>::new();
+        while array.len < N {
+            array.push(S::padding()?);
+        }
+        Some(array.finish())
+    }
+}
+
+impl<'de: 'a, 'a> DeSerialization<'de> for &'a [u8] {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        decode_bytes(ptr, ctx)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+impl<'de, S: DeSerialization<'de>> DeSerialization<'de> for Box<[S]> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let vec = Vec::<S>::decode(ptr, ctx)?;
+        Ok(vec.into_boxed_slice())
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
     }
 }
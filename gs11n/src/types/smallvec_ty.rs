@@ -0,0 +1,43 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::types::slice::{decode_elements, encode_slice, record_slice};
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use smallvec::{Array, SmallVec};
+
+// same layout as a vector, so data can be shared between them
+impl<A: Array> WireTypeTrait for SmallVec<A> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<A: Array> Serialization for SmallVec<A>
+where
+    A::Item: Serialization,
+{
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        encode_slice(self.as_slice(), ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_slice(self.as_slice(), meta_data)
+    }
+}
+
+impl<'de, A: Array> DeSerialization<'de> for SmallVec<A>
+where
+    A::Item: DeSerialization<'de>,
+{
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        // decode in place, so that no allocation happens if the elements fit in the inline buffer
+        let mut vec = Self::new();
+        decode_elements(ptr, ctx, |element| {
+            vec.push(element);
+            Ok(())
+        })?;
+        Ok(vec)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
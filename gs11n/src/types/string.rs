@@ -1,22 +1,21 @@
 use crate::decoder::{DecodeContext, DecodeError};
 use crate::meta_data::Metadata;
 use crate::serialization::wire_type::WireType;
+use crate::slice::decode_bytes;
 use crate::unsigned::EncodeSize;
 use crate::{DeSerialization, Serialization, WireTypeTrait};
-use std::mem::{size_of, MaybeUninit};
 
-fn encode_str(str: &str, ptr: &mut *mut u8) {
+pub(crate) fn encode_str(str: &str, ptr: &mut *mut u8) {
     // encode size first
     str.len().encode_raw(ptr);
     unsafe {
-        let mut p = *ptr;
-        std::ptr::copy_nonoverlapping(str.as_ptr(), p as *mut u8, str.len());
-        p = ((p as *mut u8).add(str.len())) as *mut u8;
-        *ptr = p;
+        let p = *ptr;
+        std::ptr::copy_nonoverlapping(str.as_ptr(), p, str.len());
+        *ptr = p.add(str.len());
     }
 }
 
-fn record_str(str: &str, meta_data: &mut Metadata) {
+pub(crate) fn record_str(str: &str, meta_data: &mut Metadata) {
     let mut size = str.len().varint_size();
     size += str.len() * std::mem::size_of::<u8>();
     meta_data.size = size;
@@ -36,23 +35,26 @@ impl Serialization for String {
     }
 }
 
-impl DeSerialization for String {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+impl<'de> DeSerialization<'de> for String {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         let counts = usize::decode(ptr, ctx)?;
 
         let mut string = String::new();
         unsafe {
             let vec: &mut Vec<u8> = string.as_mut_vec();
+            let start = *ptr;
+            let end = ctx.bounds_checker.end_of(start, counts)?;
             vec.reserve(counts);
+            std::ptr::copy_nonoverlapping(start, vec.as_mut_ptr(), counts);
             vec.set_len(counts);
-            let start = *ptr;
-            let end = (start).add(counts * size_of::<u8>());
-            ctx.bounds_checker.check_bounds(end.sub(1))?;
-            std::ptr::copy_nonoverlapping(start as *mut u8, vec.as_mut_ptr(), counts);
             *ptr = end;
             Ok(string)
         }
     }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
 }
 
 impl WireTypeTrait for &str {
@@ -68,3 +70,38 @@ impl Serialization for &str {
         record_str(self, meta_data);
     }
 }
+
+impl<'de: 'a, 'a> DeSerialization<'de> for &'a str {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        std::str::from_utf8(decode_bytes(ptr, ctx)?).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+impl WireTypeTrait for Box<str> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for Box<str> {
+    fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+        encode_str(self, ptr);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        record_str(self, meta_data);
+    }
+}
+
+impl<'de> DeSerialization<'de> for Box<str> {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let string = String::decode(ptr, ctx)?;
+        Ok(string.into_boxed_str())
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
@@ -0,0 +1,218 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::signed::ZigZag;
+use crate::unsigned::EncodeSize;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::convert::TryFrom;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+fn decode_nanos(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<u32, DecodeError> {
+    let nanos = u32::decode(ptr, ctx)?;
+    if nanos >= NANOS_PER_SEC {
+        Err(DecodeError::NanosOutOfRange)
+    } else {
+        Ok(nanos)
+    }
+}
+
+// A duration is encoded as seconds followed by nanoseconds, both are varint.
+impl WireTypeTrait for Duration {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for Duration {
+    fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+        self.as_secs().encode_raw(ptr);
+        self.subsec_nanos().encode_raw(ptr);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = self.as_secs().varint_size() + self.subsec_nanos().varint_size();
+    }
+}
+
+impl<'de> DeSerialization<'de> for Duration {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let secs = u64::decode(ptr, ctx)?;
+        let nanos = decode_nanos(ptr, ctx)?;
+        Ok(Duration::new(secs, nanos))
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+/// A duration which may be negative, it is encoded as zigzag seconds followed by nanoseconds, the
+/// nanoseconds are always positive, so -0.3s is encoded as -1s + 0.7s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+struct SignedDuration {
+    negative: bool,
+    duration: Duration,
+}
+
+impl SignedDuration {
+    fn parts(&self) -> (u64, u32) {
+        // durations out of the range of `i64` seconds are saturated
+        let (secs, nanos) = match i64::try_from(self.duration.as_secs()) {
+            Ok(secs) => (secs, self.duration.subsec_nanos()),
+            Err(_) if self.negative => return (i64::MIN.zigzag(), 0),
+            Err(_) => (i64::MAX, NANOS_PER_SEC - 1),
+        };
+        if !self.negative {
+            (secs.zigzag(), nanos)
+        } else if nanos == 0 {
+            ((-secs).zigzag(), 0)
+        } else {
+            ((-secs - 1).zigzag(), NANOS_PER_SEC - nanos)
+        }
+    }
+
+    fn encode(&self, ptr: &mut *mut u8) {
+        let (secs, nanos) = self.parts();
+        secs.encode_raw(ptr);
+        nanos.encode_raw(ptr);
+    }
+
+    fn size(&self) -> usize {
+        let (secs, nanos) = self.parts();
+        secs.varint_size() + nanos.varint_size()
+    }
+
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+        let secs = i64::decode(ptr, ctx)?;
+        let nanos = decode_nanos(ptr, ctx)?;
+        Ok(if secs >= 0 {
+            SignedDuration {
+                negative: false,
+                duration: Duration::new(secs as u64, nanos),
+            }
+        } else if nanos == 0 {
+            SignedDuration {
+                negative: true,
+                duration: Duration::new(secs.unsigned_abs(), 0),
+            }
+        } else {
+            SignedDuration {
+                negative: true,
+                duration: Duration::new(secs.unsigned_abs() - 1, NANOS_PER_SEC - nanos),
+            }
+        })
+    }
+}
+
+fn system_time_offset(time: &SystemTime) -> SignedDuration {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => SignedDuration {
+            negative: false,
+            duration,
+        },
+        Err(e) => SignedDuration {
+            negative: true,
+            duration: e.duration(),
+        },
+    }
+}
+
+// A system time is encoded as a signed duration since `UNIX_EPOCH`.
+impl WireTypeTrait for SystemTime {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for SystemTime {
+    fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+        system_time_offset(self).encode(ptr);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = system_time_offset(self).size();
+    }
+}
+
+impl<'de> DeSerialization<'de> for SystemTime {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        let offset = SignedDuration::decode(ptr, ctx)?;
+        let time = if offset.negative {
+            UNIX_EPOCH.checked_sub(offset.duration)
+        } else {
+            UNIX_EPOCH.checked_add(offset.duration)
+        };
+        time.ok_or(DecodeError::TimeOutOfRange)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(UNIX_EPOCH)
+    }
+}
+
+/// An `Instant` is only meaningful inside the process which creates it, so it can not be encoded
+/// directly. `InstantOffset` saves an instant as the offset to a base instant supplied by the
+/// caller, e.g. the time a level is loaded, and the instant is restored by another base later.
+/// ```
+/// use gs11n::time::InstantOffset;
+/// use std::time::{Duration, Instant};
+///
+/// let level_start = Instant::now();
+/// let cooldown_end = level_start + Duration::from_secs(3);
+/// let offset = InstantOffset::new(cooldown_end, level_start);
+///
+/// let new_level_start = Instant::now();
+/// let restored = offset.to_instant(new_level_start).unwrap();
+/// assert_eq!(restored - new_level_start, Duration::from_secs(3));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct InstantOffset(SignedDuration);
+
+impl InstantOffset {
+    /// Create an offset of `instant` relative to `base`, which can be earlier or later than
+    /// `instant`.
+    pub fn new(instant: Instant, base: Instant) -> Self {
+        InstantOffset(match instant.checked_duration_since(base) {
+            Some(duration) => SignedDuration {
+                negative: false,
+                duration,
+            },
+            None => SignedDuration {
+                negative: true,
+                duration: base.duration_since(instant),
+            },
+        })
+    }
+
+    /// Get the instant with the offset relative to `base`, `None` if the instant can not be
+    /// represented.
+    pub fn to_instant(&self, base: Instant) -> Option<Instant> {
+        if self.0.negative {
+            base.checked_sub(self.0.duration)
+        } else {
+            base.checked_add(self.0.duration)
+        }
+    }
+}
+
+impl WireTypeTrait for InstantOffset {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for InstantOffset {
+    fn encode(&self, ptr: &mut *mut u8, _meta_data: &mut Metadata) {
+        self.0.encode(ptr);
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = self.0.size();
+    }
+}
+
+impl<'de> DeSerialization<'de> for InstantOffset {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        Ok(InstantOffset(SignedDuration::decode(ptr, ctx)?))
+    }
+
+    fn padding() -> Option<Self> {
+        Some(Self::default())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::decoder::{decode_nested, DecodeContext, DecodeError};
+use crate::encoder::{encode_nested, size_of_nested};
+use crate::meta_data::Metadata;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+
+/// A tuple made only of fixed elements is itself fixed if the elements together take exactly the
+/// size of a fixed wire type, otherwise it is `LengthDelimited`.
+const fn tuple_wire_type(elements: &[WireType]) -> WireType {
+    let mut size = 0;
+    let mut i = 0;
+    while i < elements.len() {
+        if !elements[i].is_fixed_type() {
+            return WireType::LengthDelimited;
+        }
+        size += elements[i].fixed_size();
+        i += 1;
+    }
+    WireType::from_fixed_size(size)
+}
+
+// tuples are encoded positionally, just like a `#[compact]` struct, the layout of a tuple is not
+// specified, so a slice of tuples is never bulk copied
+macro_rules! s11n_for_tuple {
+    ($(($($n:tt $T:ident),+))+) => ($(
+        impl<$($T: WireTypeTrait),+> WireTypeTrait for ($($T,)+) {
+            const WIRE_TYPE: WireType = tuple_wire_type(&[$($T::WIRE_TYPE),+]);
+        }
+
+        impl<$($T: Serialization),+> Serialization for ($($T,)+) {
+            fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+                $(encode_nested(&self.$n, ptr, meta_data.get($n));)+
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                let mut size = 0;
+                $(
+                    self.$n.record(meta_data.get($n));
+                    size += size_of_nested::<$T>(meta_data.get($n));
+                )+
+                meta_data.size = size;
+            }
+        }
+
+        impl<'de, $($T: DeSerialization<'de>),+> DeSerialization<'de> for ($($T,)+) {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                Ok(($(decode_nested::<$T>(ptr, ctx)?,)+))
+            }
+
+            fn padding() -> Option<Self> {
+                Some(($($T::padding()?,)+))
+            }
+        }
+    )+)
+}
+
+s11n_for_tuple! {
+    (0 T0)
+    (0 T0, 1 T1)
+    (0 T0, 1 T1, 2 T2)
+    (0 T0, 1 T1, 2 T2, 3 T3)
+    (0 T0, 1 T1, 2 T2, 3 T3, 4 T4)
+    (0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5)
+    (0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6)
+    (0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7)
+    (0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8)
+    (0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9)
+    (0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10)
+    (0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10, 11 T11)
+}
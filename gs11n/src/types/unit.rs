@@ -0,0 +1,51 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::marker::PhantomData;
+
+// Zero sized types take no space, when used as a field, only a wired id and a zero size is encoded.
+
+impl WireTypeTrait for () {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl Serialization for () {
+    fn encode(&self, _ptr: &mut *mut u8, _meta_data: &mut Metadata) {}
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = 0
+    }
+}
+
+impl<'de> DeSerialization<'de> for () {
+    fn decode(_ptr: &mut *const u8, _ctx: &DecodeContext) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+
+    fn padding() -> Option<Self> {
+        Some(())
+    }
+}
+
+impl<T: ?Sized> WireTypeTrait for PhantomData<T> {
+    const WIRE_TYPE: WireType = WireType::LengthDelimited;
+}
+
+impl<T: ?Sized> Serialization for PhantomData<T> {
+    fn encode(&self, _ptr: &mut *mut u8, _meta_data: &mut Metadata) {}
+
+    fn record(&self, meta_data: &mut Metadata) {
+        meta_data.size = 0
+    }
+}
+
+impl<'de, T: ?Sized> DeSerialization<'de> for PhantomData<T> {
+    fn decode(_ptr: &mut *const u8, _ctx: &DecodeContext) -> Result<Self, DecodeError> {
+        Ok(PhantomData)
+    }
+
+    fn padding() -> Option<Self> {
+        Some(PhantomData)
+    }
+}
@@ -19,9 +19,8 @@ macro_rules! s11n_for_unsigned {
                 // Revert bit scan.
                 let rbs = bits - value.leading_zeros() - 1;
                 let rbs = rbs as usize;
-                // Same as (((rbs / 7) + rbs) / 8) + 1
-                let size = (rbs * 9 + 73) / 64;
-                return size;
+                // each byte holds 7 bits
+                rbs / 7 + 1
             }
 
             fn encode_raw(&self, ptr: &mut *mut u8) {
@@ -55,6 +54,7 @@ macro_rules! s11n_for_unsigned {
 
         impl WireTypeTrait for $t {
             const WIRE_TYPE: WireType = WireType::Varint;
+            const BYTES: bool = size_of::<$t>() == 1;
         }
 
         impl Serialization for $t {
@@ -67,8 +67,8 @@ macro_rules! s11n_for_unsigned {
             }
         }
 
-        impl DeSerialization for $t {
-            fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+        impl<'de> DeSerialization<'de> for $t {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
                 unsafe {
                     let mut p = *ptr;
                     ctx.bounds_checker.check_bounds(p)?;
@@ -100,8 +100,12 @@ macro_rules! s11n_for_unsigned {
                     return Result::Ok(v);
                 }
             }
+
+            fn padding() -> Option<Self> {
+                Some(Self::default())
+            }
         }
     )*)
 }
 
-s11n_for_unsigned!(usize u8 u16 u32 u64);
+s11n_for_unsigned!(usize u8 u16 u32 u64 u128);
@@ -0,0 +1,40 @@
+use crate::decoder::{DecodeContext, DecodeError};
+use crate::meta_data::Metadata;
+use crate::wire_type::WireType;
+use crate::{DeSerialization, Serialization, WireTypeTrait};
+use std::cmp::Reverse;
+use std::num::Wrapping;
+
+// transparent wrappers are encoded exactly like the value they wrap
+macro_rules! s11n_for_wrapper {
+    ($($w:ident)*) => ($(
+        impl<T: WireTypeTrait> WireTypeTrait for $w<T> {
+            const WIRE_TYPE: WireType = T::WIRE_TYPE;
+            // both wrappers are `#[repr(transparent)]`
+            const BULK_COPY: bool = T::BULK_COPY;
+            const SELF_DELIMITED: bool = T::SELF_DELIMITED;
+        }
+
+        impl<T: Serialization> Serialization for $w<T> {
+            fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+                self.0.encode(ptr, meta_data)
+            }
+
+            fn record(&self, meta_data: &mut Metadata) {
+                self.0.record(meta_data)
+            }
+        }
+
+        impl<'de, T: DeSerialization<'de>> DeSerialization<'de> for $w<T> {
+            fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+                Ok($w(T::decode(ptr, ctx)?))
+            }
+
+            fn padding() -> Option<Self> {
+                T::padding().map($w)
+            }
+        }
+    )*)
+}
+
+s11n_for_wrapper!(Wrapping Reverse);
@@ -1,4 +1,4 @@
-/// Stable way to use branch likely
+//! Stable way to use branch likely
 
 #[inline]
 #[cold]
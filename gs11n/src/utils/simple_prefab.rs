@@ -21,10 +21,10 @@ impl Serialization for SimplePrefab {
     }
 }
 
-impl DeSerialization for SimplePrefab {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+impl<'de> DeSerialization<'de> for SimplePrefab {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         let v = u64::decode(ptr, ctx)?;
-        Ok(Self { 0: v })
+        Ok(Self(v))
     }
 }
 
@@ -44,6 +44,6 @@ impl DerefMut for SimplePrefab {
 
 impl SimplePrefab {
     pub fn new(index: u64) -> Self {
-        Self { 0: index }
+        Self(index)
     }
 }
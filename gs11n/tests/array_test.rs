@@ -0,0 +1,186 @@
+use gs11n::decoder::{ArrayLengthPolicy, DecodeContext, DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use gs11n::meta_data::Metadata;
+use gs11n::{DeSerialization, Serialization, WireTypeTrait};
+use std::net::SocketAddr;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts how many times it is dropped, 0 can not be decoded.
+#[derive(Debug, PartialEq)]
+struct Counted(u8);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl WireTypeTrait for Counted {}
+
+impl Serialization for Counted {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        self.0.encode(ptr, meta_data)
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        self.0.record(meta_data)
+    }
+}
+
+impl<'de> DeSerialization<'de> for Counted {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        match u8::decode(ptr, ctx)? {
+            0 => Err(DecodeError::InvalidType),
+            value => Ok(Counted(value)),
+        }
+    }
+}
+
+#[test]
+fn array_test() {
+    let slots = [3u32, 300, 7];
+    let encoder = Encoder::from(&slots);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<[u32; 3]>().unwrap(), slots);
+
+    let names = [String::from("sword"), String::from("shield")];
+    let encoder = Encoder::from(&names);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<[String; 2]>().unwrap(), names);
+
+    // the count claims more elements than the data
+    let decoder = Decoder::from_data(&[3, 1, 2]);
+    assert_eq!(
+        decoder.decode::<[u8; 3]>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+    let decoder = Decoder::from_data(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F]);
+    assert_eq!(
+        decoder.decode::<[u32; 2]>(),
+        Err(DecodeError::ArrayLengthMismatch)
+    );
+}
+
+#[test]
+fn array_length_policy_test() {
+    let short = [1u32, 2];
+    let encoder = Encoder::from(&short);
+    let short_result = encoder.encode();
+    let long = [String::from("a"), String::from("b"), String::from("c")];
+    let encoder = Encoder::from(&long);
+    let long_result = encoder.encode();
+    let bytes = ([1u8, 2, 3], 9u8);
+    let encoder = Encoder::from(&bytes);
+    let bytes_result = encoder.encode();
+
+    let decoder = Decoder::from_data(short_result.as_slice());
+    assert_eq!(
+        decoder.decode::<[u32; 3]>(),
+        Err(DecodeError::ArrayLengthMismatch)
+    );
+    let decoder = Decoder::from_data(long_result.as_slice());
+    assert_eq!(
+        decoder.decode::<[String; 2]>(),
+        Err(DecodeError::ArrayLengthMismatch)
+    );
+
+    let decoder = Decoder::from_data(long_result.as_slice())
+        .with_array_length_policy(ArrayLengthPolicy::Truncate);
+    assert_eq!(decoder.decode::<[String; 2]>().unwrap(), ["a", "b"]);
+    let decoder = Decoder::from_data(bytes_result.as_slice())
+        .with_array_length_policy(ArrayLengthPolicy::Truncate);
+    assert_eq!(decoder.decode::<([u8; 2], u8)>(), Ok(([1, 2], 9)));
+    let decoder = Decoder::from_data(short_result.as_slice())
+        .with_array_length_policy(ArrayLengthPolicy::Truncate);
+    assert_eq!(
+        decoder.decode::<[u32; 3]>(),
+        Err(DecodeError::ArrayLengthMismatch)
+    );
+
+    let decoder = Decoder::from_data(short_result.as_slice())
+        .with_array_length_policy(ArrayLengthPolicy::Pad);
+    assert_eq!(decoder.decode::<[u32; 4]>().unwrap(), [1, 2, 0, 0]);
+    let decoder = Decoder::from_data(bytes_result.as_slice())
+        .with_array_length_policy(ArrayLengthPolicy::Pad);
+    assert_eq!(
+        decoder.decode::<([u8; 5], u8)>().unwrap(),
+        ([1, 2, 3, 0, 0], 9)
+    );
+    let decoder =
+        Decoder::from_data(long_result.as_slice()).with_array_length_policy(ArrayLengthPolicy::Pad);
+    assert_eq!(decoder.decode::<[String; 1]>().unwrap(), ["a"]);
+}
+
+#[test]
+fn array_drop_test() {
+    // the third element is not valid
+    let decoder = Decoder::from_data(&[3, 1, b'a', 1, b'b', 5, b'c']);
+    assert_eq!(
+        decoder.decode::<[String; 3]>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+
+    // decoded elements are dropped when a later one fails
+    let decoder = Decoder::from_data(&[3, 1, 2, 0]);
+    assert_eq!(
+        decoder.decode::<[Counted; 3]>(),
+        Err(DecodeError::InvalidType)
+    );
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn array_padding_test() {
+    // `Counted` has no padding value
+    let decoder = Decoder::from_data(&[1, 5]).with_array_length_policy(ArrayLengthPolicy::Pad);
+    assert_eq!(
+        decoder.decode::<[Counted; 2]>(),
+        Err(DecodeError::ArrayLengthMismatch)
+    );
+
+    let decoder = Decoder::from_data(&[1, 1, 7]).with_array_length_policy(ArrayLengthPolicy::Pad);
+    assert_eq!(decoder.decode::<[[u8; 2]; 2]>().unwrap(), [[7, 0], [0, 0]]);
+
+    let decoder =
+        Decoder::from_data(&[0, 0, 0, 0]).with_array_length_policy(ArrayLengthPolicy::Pad);
+    let (ranges, addr, time, pointers) = decoder
+        .decode::<(
+            [Range<u32>; 1],
+            [SocketAddr; 1],
+            [SystemTime; 1],
+            [Rc<String>; 1],
+        )>()
+        .unwrap();
+    assert_eq!(ranges[0], 0..0);
+    assert_eq!(addr, [SocketAddr::from(([0, 0, 0, 0], 0))]);
+    assert_eq!(time, [UNIX_EPOCH]);
+    assert_eq!(pointers, [Rc::new(String::new())]);
+}
+
+#[test]
+fn corrupted_counts_test() {
+    // the count of floats overflows when multiplied by their size
+    let decoder =
+        Decoder::from_data(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x1, 0]);
+    assert_eq!(
+        decoder.decode::<Vec<f32>>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+    let decoder = Decoder::from_data(&[2, 0, 0, 0, 0]);
+    assert_eq!(
+        decoder.decode::<Vec<f32>>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+    let decoder = Decoder::from_data(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F, 1]);
+    assert_eq!(
+        decoder.decode::<Vec<u32>>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
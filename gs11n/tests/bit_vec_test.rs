@@ -0,0 +1,47 @@
+use gs11n::bit_vec::BitVec;
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+
+#[test]
+fn bit_vec_test() {
+    let mut fog: BitVec = vec![true, false, false, true, true, false, true, false, true].into();
+    let encoder = Encoder::from(&fog);
+    let buffer = encoder.encode();
+    assert_eq!(buffer, vec![9, 0b0101_1001, 0b1]);
+    let decoder = Decoder::from_data(buffer.as_slice());
+    assert_eq!(decoder.decode::<BitVec>().unwrap(), fog);
+
+    fog.set(8, false);
+    fog.push(true);
+    assert_eq!(fog.get(8), Some(false));
+    assert_eq!(fog.get(9), Some(true));
+    assert_eq!(fog.get(10), None);
+    let encoder = Encoder::from(&fog);
+    assert_eq!(encoder.encode(), vec![10, 0b0101_1001, 0b10]);
+
+    let grid = BitVec::from_elem(100_000, true);
+    let encoder = Encoder::from(&grid);
+    let buffer = encoder.encode();
+    assert_eq!(buffer.len(), 3 + 12_500);
+    let decoder = Decoder::from_data(buffer.as_slice());
+    assert_eq!(decoder.decode::<BitVec>().unwrap(), grid);
+
+    // unused bits of the last byte are ignored
+    let decoder = Decoder::from_data(&[3, 0xFF]);
+    assert_eq!(
+        Vec::<bool>::from(decoder.decode::<BitVec>().unwrap()),
+        vec![true; 3]
+    );
+
+    // a corrupted count of values fails without reading out of the data
+    let decoder = Decoder::from_data(&[17, 0xFF, 0xFF]);
+    assert_eq!(
+        decoder.decode::<BitVec>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+    let decoder = Decoder::from_data(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]);
+    assert_eq!(
+        decoder.decode::<BitVec>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
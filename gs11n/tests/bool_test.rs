@@ -0,0 +1,71 @@
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use std::marker::PhantomData;
+
+#[test]
+fn bool_test() {
+    let encoder = Encoder::from(&true);
+    let buffer = encoder.encode();
+    assert_eq!(buffer, vec![1]);
+    let decoder = Decoder::from_data(buffer.as_slice());
+    assert!(decoder.decode::<bool>().unwrap());
+
+    let decoder = Decoder::from_data(&[2]);
+    assert_eq!(decoder.decode::<bool>(), Err(DecodeError::InvalidBool));
+}
+
+#[test]
+fn bool_vector_test() {
+    let flags = vec![true, false, false, true];
+    let encoder = Encoder::from(&flags);
+    let buffer = encoder.encode();
+    assert_eq!(buffer, vec![4, 1, 0, 0, 1]);
+    let decoder = Decoder::from_data(buffer.as_slice());
+    let flags2: Vec<bool> = decoder.decode().unwrap();
+    assert_eq!(flags, flags2);
+
+    // invalid values must not be copied into a vector
+    let decoder = Decoder::from_data(&[3, 1, 0xFF, 0]);
+    assert_eq!(decoder.decode::<Vec<bool>>(), Err(DecodeError::InvalidBool));
+}
+
+#[test]
+fn zero_sized_test() {
+    let units = vec![(), (), ()];
+    let encoder = Encoder::from(&units);
+    let buffer = encoder.encode();
+    assert_eq!(buffer, vec![3]);
+    let decoder = Decoder::from_data(buffer.as_slice());
+    let units2: Vec<()> = decoder.decode().unwrap();
+    assert_eq!(units, units2);
+
+    // zero sized values must be distinguishable from None
+    let marker: Option<PhantomData<String>> = Some(PhantomData);
+    let encoder = Encoder::from(&marker);
+    let buffer = encoder.encode();
+    assert_eq!(buffer, vec![1, 0]);
+    let decoder = Decoder::from_data(buffer.as_slice());
+    let marker2: Option<PhantomData<String>> = decoder.decode().unwrap();
+    assert_eq!(marker, marker2);
+
+    let marker: Option<()> = None;
+    let encoder = Encoder::from(&marker);
+    let buffer = encoder.encode();
+    assert_eq!(buffer, vec![0]);
+    let decoder = Decoder::from_data(buffer.as_slice());
+    let marker2: Option<()> = decoder.decode().unwrap();
+    assert_eq!(marker, marker2);
+}
+
+#[test]
+fn zero_sized_compatibility_test() {
+    // older versions encoded `Some` of a zero sized value the same as `None`
+    let decoder = Decoder::from_data(&[0]);
+    let marker: Option<()> = decoder.decode().unwrap();
+    assert_eq!(marker, None);
+
+    // and other values are encoded the same as before
+    let decoder = Decoder::from_data(&[1, 5]);
+    let value: Option<u32> = decoder.decode().unwrap();
+    assert_eq!(value, Some(5));
+}
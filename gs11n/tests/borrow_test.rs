@@ -0,0 +1,78 @@
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+#[test]
+fn borrowed_str_test() {
+    let names = vec!["goblin", "", "troll"];
+    let encoder = Encoder::from(&names);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_names = decoder.decode::<Vec<&str>>().unwrap();
+    assert_eq!(decode_names, names);
+
+    // the strings point into the encoded data
+    let data = encode_result.as_ptr_range();
+    assert!(data.contains(&decode_names[0].as_ptr()));
+    assert!(data.contains(&decode_names[2].as_ptr()));
+
+    let decoder = Decoder::from_data(&[2, 0xC3, 0x28]);
+    assert_eq!(decoder.decode::<&str>(), Err(DecodeError::InvalidUtf8));
+    let decoder = Decoder::from_data(&[3, b'a', b'b']);
+    assert_eq!(
+        decoder.decode::<&str>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
+
+#[test]
+fn borrowed_bytes_test() {
+    let payload: &[u8] = &[0xDE, 0xAD, 0xBE, 0xEF];
+    let value = (7u32, payload);
+    let encoder = Encoder::from(&value);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![7, 4, 0xDE, 0xAD, 0xBE, 0xEF]);
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let (id, bytes) = decoder.decode::<(u32, &[u8])>().unwrap();
+    assert_eq!(id, 7);
+    assert_eq!(bytes, payload);
+    assert_eq!(bytes.as_ptr(), encode_result[2..].as_ptr());
+
+    let decoder = Decoder::from_data(&[3, 1, 2]);
+    assert_eq!(
+        decoder.decode::<&[u8]>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+
+    // a `Cow` of bytes is encoded like the borrowed bytes
+    let cow: Cow<[u8]> = Cow::Borrowed(&[200, 1]);
+    let encode_result = Encoder::from(&cow).encode();
+    assert_eq!(encode_result, vec![2, 200, 1]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Cow<[u8]>>().unwrap(), cow);
+
+    // slices of slices of bytes are not bytes
+    let chunks: Vec<&[u8]> = vec![&[200], &[]];
+    let encode_result = Encoder::from(&chunks).encode();
+    assert_eq!(encode_result, vec![2, 1, 200, 0]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<&[u8]>>().unwrap(), chunks);
+}
+
+#[test]
+fn byte_sequence_test() {
+    // owned sequences of bytes are sequences of varints
+    let bytes = vec![200u8, 1];
+    let encode_result = Encoder::from(&bytes).encode();
+    assert_eq!(encode_result, vec![2, 200, 1, 1]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<u8>>().unwrap(), bytes);
+
+    let queue: VecDeque<u8> = bytes.iter().copied().collect();
+    assert_eq!(Encoder::from(&queue).encode(), encode_result);
+    assert_eq!(Encoder::from(&[200u8, 1]).encode(), encode_result);
+    let boxed: Box<[u8]> = bytes.clone().into_boxed_slice();
+    assert_eq!(Encoder::from(&boxed).encode(), encode_result);
+}
@@ -0,0 +1,80 @@
+use gs11n::decoder::Decoder;
+use gs11n::encoder::Encoder;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::{Mutex, RwLock};
+
+type Cells = (
+    Cell<f32>,
+    RefCell<Vec<u32>>,
+    Mutex<String>,
+    RwLock<Option<i32>>,
+);
+
+#[test]
+fn cell_test() {
+    let value = (
+        Cell::new(1.5f32),
+        RefCell::new(vec![1u32, 2, 3]),
+        Mutex::new(String::from("state")),
+        RwLock::new(Some(-1i32)),
+    );
+    let encoder = Encoder::from(&value);
+    let encode_result = encoder.encode();
+    let expect = (1.5f32, vec![1u32, 2, 3], String::from("state"), Some(-1i32));
+    assert_eq!(encode_result, Encoder::from(&expect).encode());
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_value: Cells = decoder.decode().unwrap();
+    assert_eq!(decode_value.0.get(), 1.5);
+    assert_eq!(*decode_value.1.borrow(), vec![1, 2, 3]);
+    assert_eq!(*decode_value.2.lock().unwrap(), "state");
+    assert_eq!(*decode_value.3.read().unwrap(), Some(-1));
+}
+
+#[test]
+fn cell_changed_test() {
+    let value = RefCell::new(vec![1u32, 2, 3]);
+    let encoder = Encoder::from(&value);
+    // the value is encoded as it is when the encoder is created
+    value.borrow_mut()[0] = 4;
+    value.borrow_mut().push(5);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<u32>>().unwrap(), vec![1, 2, 3]);
+
+    let encode_result = Encoder::from(&value).encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<u32>>().unwrap(), vec![4, 2, 3, 5]);
+}
+
+#[test]
+fn cell_shared_test() {
+    let name = Rc::new(String::from("shared"));
+    let value = (RefCell::new(vec![name.clone(), name.clone()]), name);
+    let encoder = Encoder::from_shared(&value);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, encoder.encode());
+
+    let decoder = Decoder::from_data(encode_result.as_slice()).with_shared_references();
+    let (names, name): (RefCell<Vec<Rc<String>>>, Rc<String>) = decoder.decode().unwrap();
+    let names = names.borrow();
+    assert_eq!(*name, "shared");
+    assert!(Rc::ptr_eq(&names[0], &name));
+    assert!(Rc::ptr_eq(&names[1], &name));
+}
+
+#[test]
+fn nested_cell_test() {
+    let inner = Rc::new(RefCell::new(vec![1u32, 2]));
+    let value = RefCell::new(vec![inner.clone(), inner.clone()]);
+    let encoder = Encoder::from(&value);
+    // the inner cells are a part of the snapshot of the outer one
+    inner.borrow_mut().push(3);
+    let encode_result = encoder.encode();
+    let expect = vec![vec![1u32, 2], vec![1, 2]];
+    assert_eq!(encode_result, Encoder::from(&expect).encode());
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<Vec<u32>>>().unwrap(), expect);
+}
@@ -0,0 +1,68 @@
+use gs11n::decoder::Decoder;
+use gs11n::encoder::Encoder;
+use std::collections::{BTreeSet, BinaryHeap, HashSet, LinkedList, VecDeque};
+
+#[test]
+fn set_test() {
+    let hash_set: HashSet<String> = ["sword", "shield", "potion"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let encoder = Encoder::from(&hash_set);
+    let encode_result = encoder.encode();
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_set: BTreeSet<String> = decoder.decode().unwrap();
+    assert_eq!(decode_set.len(), 3);
+    for item in &hash_set {
+        assert!(decode_set.contains(item));
+    }
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_set: HashSet<String> = decoder.decode().unwrap();
+    assert_eq!(decode_set, hash_set);
+}
+
+#[test]
+fn queue_test() {
+    let mut deque: VecDeque<u32> = VecDeque::new();
+    deque.push_back(2);
+    deque.push_back(3);
+    deque.push_front(1);
+    let encoder = Encoder::from(&deque);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![3, 1, 2, 3]);
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_list: LinkedList<u32> = decoder.decode().unwrap();
+    assert_eq!(
+        decode_list.iter().copied().collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+
+    let encoder = Encoder::from(&decode_list);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_deque: VecDeque<u32> = decoder.decode().unwrap();
+    assert_eq!(decode_deque, deque);
+
+    let mut float_deque: VecDeque<f32> = VecDeque::new();
+    float_deque.push_back(2.0);
+    float_deque.push_front(1.0);
+    let encoder = Encoder::from(&float_deque);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_deque: VecDeque<f32> = decoder.decode().unwrap();
+    assert_eq!(decode_deque, float_deque);
+}
+
+#[test]
+fn heap_test() {
+    let heap: BinaryHeap<i32> = vec![5, -1, 3, 8].into();
+    let encoder = Encoder::from(&heap);
+    let encode_result = encoder.encode();
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_heap: BinaryHeap<i32> = decoder.decode().unwrap();
+    assert_eq!(decode_heap.into_sorted_vec(), vec![-1, 3, 5, 8]);
+}
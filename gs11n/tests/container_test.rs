@@ -0,0 +1,70 @@
+#![cfg(any(feature = "smallvec", feature = "arrayvec", feature = "indexmap"))]
+
+use gs11n::decoder::Decoder;
+use gs11n::encoder::Encoder;
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn small_vec_test() {
+    use smallvec::{smallvec, SmallVec};
+
+    let inline: SmallVec<[u32; 4]> = smallvec![1, 300, 7];
+    let encoder = Encoder::from(&inline);
+    let encode_result = encoder.encode();
+    // same as a vector
+    assert_eq!(encode_result, Encoder::from(&vec![1u32, 300, 7]).encode());
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decoded = decoder.decode::<SmallVec<[u32; 4]>>().unwrap();
+    assert_eq!(decoded, inline);
+    assert!(!decoded.spilled());
+
+    let spilled: SmallVec<[f32; 2]> = smallvec![1.0, 2.0, 3.0];
+    let encoder = Encoder::from(&spilled);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<SmallVec<[f32; 2]>>().unwrap(), spilled);
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn array_vec_test() {
+    use arrayvec::ArrayVec;
+    use gs11n::decoder::DecodeError;
+
+    let slots: ArrayVec<String, 4> = ["sword", "shield"].iter().map(|s| s.to_string()).collect();
+    let encoder = Encoder::from(&slots);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<ArrayVec<String, 4>>().unwrap(), slots);
+
+    // more elements than the capacity
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(
+        decoder.decode::<ArrayVec<String, 1>>(),
+        Err(DecodeError::CapacityExceeded)
+    );
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn index_map_test() {
+    use indexmap::{IndexMap, IndexSet};
+
+    let mut stats = IndexMap::new();
+    stats.insert(String::from("strength"), 10u32);
+    stats.insert(String::from("agility"), 7);
+    stats.insert(String::from("intellect"), 3);
+    let encoder = Encoder::from(&stats);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decoded = decoder.decode::<IndexMap<String, u32>>().unwrap();
+    assert!(decoded.iter().eq(stats.iter()));
+
+    let tags: IndexSet<i32> = [5, -1, 3].iter().copied().collect();
+    let encoder = Encoder::from(&tags);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![3, 10, 1, 6]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decoded = decoder.decode::<IndexSet<i32>>().unwrap();
+    assert!(decoded.iter().eq(tags.iter()));
+}
@@ -11,7 +11,8 @@ pub trait ToString {
     fn dyn_record(&self, meta_data: &mut gs11n::meta_data::Metadata);
 }
 
-const _DYN_METADATA_FOR_TO_STRING: () = {
+// anonymous const, so impls inside it are not considered as non-local definitions
+const _: () = {
     lazy_static::lazy_static! {
         static ref VTABLE : gs11n::dynamic::VTable<dyn ToString>
             =  std::sync::RwLock::new(rustc_hash::FxHashMap::default());
@@ -36,10 +37,10 @@ const _DYN_METADATA_FOR_TO_STRING: () = {
         }
     }
 
-    impl gs11n::DeSerialization for Box<dyn ToString> {
+    impl<'de> gs11n::DeSerialization<'de> for Box<dyn ToString> {
         fn decode(
             ptr: &mut *const u8,
-            ctx: &gs11n::decoder::DecodeContext,
+            ctx: &gs11n::decoder::DecodeContext<'de>,
         ) -> Result<Self, gs11n::decoder::DecodeError> {
             let id = usize::decode(ptr, ctx)?;
             let v_table = REF_VTABLE.load(std::sync::atomic::Ordering::Relaxed);
@@ -83,10 +84,13 @@ const _DYN_METADATA_FOR_TO_STRING: () = {
 
     #[ctor::ctor]
     unsafe fn register_trait() {
-        let vtable: &gs11n::dynamic::VTable<dyn ToString> = &*VTABLE;
+        let vtable: &gs11n::dynamic::VTable<dyn ToString> = &VTABLE;
         let mut register = gs11n::plugin::REGISTERED_TRAITS.lock().unwrap();
         let trait_info = gs11n::plugin::TraitInfo {
-            vtable: std::mem::transmute(vtable),
+            vtable: std::mem::transmute::<
+                &gs11n::dynamic::VTable<dyn ToString>,
+                &gs11n::plugin::UnsafeVTable,
+            >(vtable),
             update_fn: sync_trait,
         };
         register.insert(
@@ -187,8 +191,7 @@ fn register_type_char_for_to_string() {
 fn get_encode_buffer() -> Vec<u8> {
     let b1: Box<dyn ToString> = Box::new(256i32);
     let encoder = Encoder::from(&b1);
-    let v = encoder.encode();
-    v
+    encoder.encode()
 }
 
 #[test]
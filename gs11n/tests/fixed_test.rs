@@ -0,0 +1,53 @@
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use gs11n::fixed::Fixed;
+use gs11n::wire_type::WireType;
+use gs11n::{DeSerialization, WireTypeTrait};
+
+#[test]
+fn fixed_test() {
+    assert_eq!(Fixed::<u8>::WIRE_TYPE, WireType::Bits8);
+    assert_eq!(Fixed::<i16>::WIRE_TYPE, WireType::Bits16);
+    assert_eq!(Fixed::<u32>::WIRE_TYPE, WireType::Bits32);
+    assert_eq!(Fixed::<i64>::WIRE_TYPE, WireType::Bits64);
+
+    let seed = Fixed(1u32);
+    let encoder = Encoder::from(&seed);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![1, 0, 0, 0]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Fixed<u32>>().unwrap(), seed);
+
+    let hashes = vec![Fixed(-1i64), Fixed(0x0102_0304_0506_0708)];
+    let encoder = Encoder::from(&hashes);
+    let encode_result = encoder.encode();
+    let mut expect = vec![2u8];
+    expect.extend_from_slice(&(-1i64).to_le_bytes());
+    expect.extend_from_slice(&0x0102_0304_0506_0708i64.to_le_bytes());
+    assert_eq!(encode_result, expect);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<Fixed<i64>>>().unwrap(), hashes);
+
+    let decoder = Decoder::from_data(&encode_result[..12]);
+    assert_eq!(
+        decoder.decode::<Vec<Fixed<i64>>>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
+
+#[test]
+fn fixed_skip_test() {
+    let value = (Fixed(7u8), Fixed(-7i16), Fixed(u32::MAX), Fixed(1u64), 5u8);
+    let encoder = Encoder::from(&value);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result.len(), 1 + 2 + 4 + 8 + 1);
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let ctx = decoder.get_context();
+    let mut ptr = encode_result.as_ptr();
+    ctx.skip(&mut ptr, WireType::Bits8).unwrap();
+    ctx.skip(&mut ptr, WireType::Bits16).unwrap();
+    ctx.skip(&mut ptr, WireType::Bits32).unwrap();
+    ctx.skip(&mut ptr, WireType::Bits64).unwrap();
+    assert_eq!(u8::decode(&mut ptr, ctx).unwrap(), 5);
+}
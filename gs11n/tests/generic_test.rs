@@ -1,16 +1,16 @@
 use gs11n::decoder::Decoder;
 use gs11n::encoder::Encoder;
-use gs11n::{DeSerialization, Serialization, WireTypeTrait};
+use gs11n::{DeSerializationOwned, Serialization, WireTypeTrait};
 
 #[derive(PartialEq, Debug)]
-struct Position<T: Serialization + DeSerialization + Default> {
+struct Position<T: Serialization + DeSerializationOwned + Default> {
     // #[serialized(0)]
     x: T,
     // #[serialized(1)]
     y: T,
 }
 
-impl<T: Serialization + DeSerialization + Default> Default for Position<T> {
+impl<T: Serialization + DeSerializationOwned + Default> Default for Position<T> {
     fn default() -> Self {
         Self {
             x: T::default(),
@@ -19,9 +19,9 @@ impl<T: Serialization + DeSerialization + Default> Default for Position<T> {
     }
 }
 
-impl<T: Serialization + DeSerialization + Default> WireTypeTrait for Position<T> {}
+impl<T: Serialization + DeSerializationOwned + Default> WireTypeTrait for Position<T> {}
 
-impl<T: Serialization + DeSerialization + Default> gs11n::Serialization for Position<T> {
+impl<T: Serialization + DeSerializationOwned + Default> gs11n::Serialization for Position<T> {
     fn encode(&self, ptr: &mut *mut u8, meta_data: &mut gs11n::meta_data::Metadata) {
         gs11n::encoder::encode_field(0, &self.x, ptr, meta_data.get(0));
         gs11n::encoder::encode_field(1, &self.y, ptr, meta_data.get(1));
@@ -35,10 +35,12 @@ impl<T: Serialization + DeSerialization + Default> gs11n::Serialization for Posi
     }
 }
 
-impl<T: Serialization + DeSerialization + Default> gs11n::DeSerialization for Position<T> {
+impl<'de, T: Serialization + DeSerializationOwned + Default> gs11n::DeSerialization<'de>
+    for Position<T>
+{
     fn decode(
         ptr: &mut *const u8,
-        ctx: &gs11n::serialization::decoder::DecodeContext,
+        ctx: &gs11n::serialization::decoder::DecodeContext<'de>,
     ) -> Result<Self, gs11n::decoder::DecodeError> {
         use gs11n::decoder::decode_wired_id;
         use gs11n::wire_type::WireType;
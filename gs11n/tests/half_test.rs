@@ -0,0 +1,44 @@
+#![cfg(feature = "half")]
+
+use gs11n::decoder::Decoder;
+use gs11n::encoder::Encoder;
+use gs11n::wire_type::WireType;
+use gs11n::WireTypeTrait;
+use half::{bf16, f16};
+
+#[test]
+fn half_test() {
+    assert_eq!(f16::WIRE_TYPE, WireType::Bits16);
+    assert_eq!(bf16::WIRE_TYPE, WireType::Bits16);
+
+    let value = f16::from_f32(1.5);
+    let encoder = Encoder::from(&value);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![0x00, 0x3E]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<f16>().unwrap(), value);
+
+    let value = bf16::from_f32(-2.0);
+    let encoder = Encoder::from(&value);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![0x00, 0xC0]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<bf16>().unwrap(), value);
+}
+
+#[test]
+fn half_vector_test() {
+    let curve: Vec<f16> = [0.0f32, 0.25, 0.5, 1.0, -65504.0]
+        .iter()
+        .map(|&v| f16::from_f32(v))
+        .collect();
+    let encoder = Encoder::from(&curve);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result.len(), 1 + 2 * curve.len());
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<f16>>().unwrap(), curve);
+
+    // not enough data for a half float
+    let decoder = Decoder::from_data(&[0x00]);
+    assert!(decoder.decode::<f16>().is_err());
+}
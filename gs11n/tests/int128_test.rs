@@ -0,0 +1,77 @@
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use gs11n::fixed::Fixed;
+use gs11n::unsigned::EncodeSize;
+use gs11n::wire_type::WireType;
+use gs11n::DeSerialization;
+
+#[test]
+fn varint_128_test() {
+    assert_eq!(0u128.varint_size(), 1);
+    assert_eq!(u64::MAX.varint_size(), 10);
+    assert_eq!(((1u128 << 70) - 1).varint_size(), 10);
+    assert_eq!((1u128 << 70).varint_size(), 11);
+    assert_eq!(u128::MAX.varint_size(), 19);
+
+    for v in [0u128, 1, 0x80, u64::MAX as u128, 1 << 70, u128::MAX] {
+        let encoder = Encoder::from(&v);
+        let encode_result = encoder.encode();
+        assert_eq!(encode_result.len(), v.varint_size());
+        let decoder = Decoder::from_data(encode_result.as_slice());
+        assert_eq!(decoder.decode::<u128>().unwrap(), v);
+    }
+
+    for v in [0i128, -1, 1, i64::MIN as i128, i128::MIN, i128::MAX] {
+        let encoder = Encoder::from(&v);
+        let encode_result = encoder.encode();
+        let decoder = Decoder::from_data(encode_result.as_slice());
+        assert_eq!(decoder.decode::<i128>().unwrap(), v);
+    }
+}
+
+#[test]
+fn fixed_128_test() {
+    let guid = Fixed(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128);
+    let encoder = Encoder::from(&guid);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, guid.0.to_le_bytes().to_vec());
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Fixed<u128>>().unwrap(), guid);
+
+    let guids = vec![Fixed(-1i128), Fixed(i128::MIN), Fixed(42)];
+    let encoder = Encoder::from(&guids);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result.len(), 1 + 16 * 3);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<Fixed<i128>>>().unwrap(), guids);
+
+    let decoder = Decoder::from_data(&encode_result[1..16]);
+    assert_eq!(
+        decoder.decode::<Fixed<i128>>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
+
+#[test]
+fn skip_128_test() {
+    let value = (u128::MAX, Fixed(7u128), 1u8);
+    let encoder = Encoder::from(&value);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result.len(), 19 + 16 + 1);
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let ctx = decoder.get_context();
+    let mut ptr = encode_result.as_ptr();
+    ctx.skip(&mut ptr, WireType::Varint).unwrap();
+    ctx.skip(&mut ptr, WireType::Bits128).unwrap();
+    assert_eq!(u8::decode(&mut ptr, ctx).unwrap(), 1);
+
+    let decoder = Decoder::from_data(&encode_result[..30]);
+    let ctx = decoder.get_context();
+    let mut ptr = encode_result.as_ptr();
+    ctx.skip(&mut ptr, WireType::Varint).unwrap();
+    assert_eq!(
+        ctx.skip(&mut ptr, WireType::Bits128),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
@@ -0,0 +1,90 @@
+#![cfg(any(feature = "glam", feature = "mint"))]
+
+use gs11n::decoder::{ArrayLengthPolicy, Decoder};
+use gs11n::encoder::Encoder;
+use gs11n::WireTypeTrait;
+
+/// Encode floats one by one, which is how math types should be encoded.
+fn encode_floats(floats: &[f32]) -> Vec<u8> {
+    floats.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn bulk_copy<T: WireTypeTrait>() -> bool {
+    T::BULK_COPY
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn glam_test() {
+    use glam::{Mat4, Quat, Vec2, Vec3, Vec3A};
+    use gs11n::wire_type::WireType;
+
+    assert_eq!(Vec2::WIRE_TYPE, WireType::Bits64);
+    assert_eq!(Quat::WIRE_TYPE, WireType::Bits128);
+    assert_eq!(Vec3::WIRE_TYPE, WireType::LengthDelimited);
+    assert!(bulk_copy::<Vec3>());
+    assert!(bulk_copy::<Mat4>());
+    assert!(!bulk_copy::<Vec3A>());
+
+    let path = vec![Vec3::new(1.0, 2.0, 3.0), Vec3::new(-0.5, 0.0, 8.25)];
+    let encoder = Encoder::from(&path);
+    let encode_result = encoder.encode();
+    let mut expect = vec![2u8];
+    expect.extend(encode_floats(&[1.0, 2.0, 3.0, -0.5, 0.0, 8.25]));
+    assert_eq!(encode_result, expect);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<Vec3>>().unwrap(), path);
+
+    // padded types are encoded the same, but not copied as a whole
+    let padded: Vec<Vec3A> = path.iter().map(|&v| v.into()).collect();
+    assert_eq!(Encoder::from(&padded).encode(), expect);
+    let decoder = Decoder::from_data(expect.as_slice());
+    assert_eq!(decoder.decode::<Vec<Vec3A>>().unwrap(), padded);
+
+    let transform = Mat4::from_scale_rotation_translation(
+        Vec3::splat(2.0),
+        Quat::from_rotation_y(0.5),
+        Vec3::new(1.0, 2.0, 3.0),
+    );
+    let encoder = Encoder::from(&transform);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, encode_floats(&transform.to_cols_array()));
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Mat4>().unwrap(), transform);
+}
+
+#[cfg(feature = "mint")]
+#[test]
+fn mint_test() {
+    use mint::{ColumnMatrix2, Quaternion, Vector3};
+
+    assert!(bulk_copy::<Vector3<f32>>());
+    assert!(bulk_copy::<Quaternion<f64>>());
+
+    let rotation = Quaternion {
+        v: Vector3::from([0.0f32, 0.6, 0.0]),
+        s: 0.8,
+    };
+    let encoder = Encoder::from(&rotation);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, encode_floats(&[0.0, 0.6, 0.0, 0.8]));
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Quaternion<f32>>().unwrap(), rotation);
+
+    let matrices = vec![ColumnMatrix2::from([1.0f64, 0.0, 0.0, 1.0]); 3];
+    let encoder = Encoder::from(&matrices);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result.len(), 1 + 3 * 4 * 8);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(
+        decoder.decode::<Vec<ColumnMatrix2<f64>>>().unwrap(),
+        matrices
+    );
+
+    // padded with zeros
+    let decoder = Decoder::from_data(&[0]).with_array_length_policy(ArrayLengthPolicy::Pad);
+    assert_eq!(
+        decoder.decode::<[Vector3<f32>; 1]>().unwrap(),
+        [Vector3::from([0.0; 3])]
+    );
+}
@@ -0,0 +1,71 @@
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+#[test]
+fn ip_addr_test() {
+    let v4 = Ipv4Addr::new(192, 168, 1, 20);
+    let encoder = Encoder::from(&v4);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![192, 168, 1, 20]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Ipv4Addr>().unwrap(), v4);
+
+    let v6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    let encoder = Encoder::from(&v6);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, v6.octets().to_vec());
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Ipv6Addr>().unwrap(), v6);
+
+    let ips = vec![
+        IpAddr::V4(v4),
+        IpAddr::V6(v6),
+        IpAddr::V4(Ipv4Addr::LOCALHOST),
+    ];
+    let encoder = Encoder::from(&ips);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<IpAddr>>().unwrap(), ips);
+
+    let ip = IpAddr::V4(v4);
+    let encoder = Encoder::from(&ip);
+    assert_eq!(encoder.encode(), vec![4, 192, 168, 1, 20]);
+
+    let decoder = Decoder::from_data(&[5, 192, 168, 1, 20]);
+    assert_eq!(decoder.decode::<IpAddr>(), Err(DecodeError::InvalidType));
+    let decoder = Decoder::from_data(&[192, 168, 1]);
+    assert_eq!(
+        decoder.decode::<Ipv4Addr>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
+
+#[test]
+fn socket_addr_test() {
+    let v4 = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 27015);
+    let encoder = Encoder::from(&v4);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![10, 0, 0, 1, 0x87, 0x69]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<SocketAddrV4>().unwrap(), v4);
+
+    let v6 = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 7777, 0, 300);
+    let encoder = Encoder::from(&v6);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result.len(), 16 + 2 + 1 + 2);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<SocketAddrV6>().unwrap(), v6);
+
+    let servers = vec![SocketAddr::V4(v4), SocketAddr::V6(v6)];
+    let encoder = Encoder::from(&servers);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<SocketAddr>>().unwrap(), servers);
+
+    let decoder = Decoder::from_data(&[0, 10, 0, 0, 1, 0x87, 0x69]);
+    assert_eq!(
+        decoder.decode::<SocketAddr>(),
+        Err(DecodeError::InvalidType)
+    );
+}
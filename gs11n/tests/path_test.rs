@@ -0,0 +1,87 @@
+use gs11n::decoder::{ArrayLengthPolicy, DecodeError, Decoder};
+use gs11n::encoder::{EncodeError, Encoder};
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+#[test]
+fn path_test() {
+    let mods = vec![
+        PathBuf::from("mods/orcs/manifest.toml"),
+        PathBuf::from("/srv/game/maps/desert.map"),
+        PathBuf::new(),
+    ];
+    let encoder = Encoder::from(&mods);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<PathBuf>>().unwrap(), mods);
+
+    // the same as a string
+    let encoder = Encoder::from(&"mods/orcs");
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(
+        decoder.decode::<PathBuf>().unwrap(),
+        PathBuf::from("mods/orcs")
+    );
+
+    let decoder = Decoder::from_data(&[2, 0xC3, 0x28]);
+    assert_eq!(decoder.decode::<PathBuf>(), Err(DecodeError::InvalidUtf8));
+
+    // arrays of paths can be padded by empty paths
+    let encode_result = Encoder::from(&[PathBuf::from("mods/orcs")]).encode();
+    let decoder = Decoder::from_data(encode_result.as_slice())
+        .with_array_length_policy(ArrayLengthPolicy::Pad);
+    assert_eq!(
+        decoder.decode::<[PathBuf; 2]>().unwrap(),
+        [PathBuf::from("mods/orcs"), PathBuf::new()]
+    );
+}
+
+#[test]
+fn os_string_test() {
+    let name = OsString::from("dedicated-server");
+    let encoder = Encoder::from(&name);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result[0], 16);
+    assert_eq!(&encode_result[1..], b"dedicated-server");
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<OsString>().unwrap(), name);
+
+    let decoder = Decoder::from_data(&[1, 0xFF]);
+    assert_eq!(decoder.decode::<OsString>(), Err(DecodeError::InvalidUtf8));
+
+    let decoder = Decoder::from_data(&[0]).with_array_length_policy(ArrayLengthPolicy::Pad);
+    assert_eq!(
+        decoder.decode::<[OsString; 1]>().unwrap(),
+        [OsString::new()]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+#[should_panic(expected = "is not valid UTF-8")]
+fn non_utf8_path_test() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = PathBuf::from(std::ffi::OsStr::from_bytes(&[b'a', 0xFF]));
+    Encoder::from(&path).encode();
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_path_error_test() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let paths = vec![
+        PathBuf::from("mods/orcs"),
+        PathBuf::from(std::ffi::OsStr::from_bytes(&[b'a', 0xFF])),
+    ];
+    assert_eq!(
+        Encoder::from(&paths).try_encode(),
+        Err(EncodeError::InvalidUtf8)
+    );
+    assert_eq!(
+        Encoder::from(&paths[0]).try_encode().unwrap(),
+        Encoder::from(&paths[0]).encode()
+    );
+}
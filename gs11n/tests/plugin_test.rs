@@ -12,7 +12,8 @@ pub trait ToString {
     fn dyn_record(&self, meta_data: &mut gs11n::meta_data::Metadata);
 }
 
-const _DYN_METADATA_FOR_TO_STRING: () = {
+// anonymous const, so impls inside it are not considered as non-local definitions
+const _: () = {
     lazy_static::lazy_static! {
         static ref VTABLE : gs11n::dynamic::VTable<dyn ToString>
             =  std::sync::RwLock::new(rustc_hash::FxHashMap::default());
@@ -34,10 +35,10 @@ const _DYN_METADATA_FOR_TO_STRING: () = {
             self.dyn_record(meta_data)
         }
     }
-    impl gs11n::DeSerialization for Box<dyn ToString> {
+    impl<'de> gs11n::DeSerialization<'de> for Box<dyn ToString> {
         fn decode(
             ptr: &mut *const u8,
-            ctx: &gs11n::decoder::DecodeContext,
+            ctx: &gs11n::decoder::DecodeContext<'de>,
         ) -> Result<Self, gs11n::decoder::DecodeError> {
             let id = usize::decode(ptr, ctx)?;
             let v_table = REF_VTABLE.load(std::sync::atomic::Ordering::Relaxed);
@@ -82,9 +83,12 @@ const _DYN_METADATA_FOR_TO_STRING: () = {
     #[ctor::ctor]
     unsafe fn register_trait() {
         let mut register = gs11n::plugin::REGISTERED_TRAITS.lock().unwrap();
-        let vtable: &VTable<dyn ToString> = &*VTABLE;
+        let vtable: &VTable<dyn ToString> = &VTABLE;
         let trait_info = gs11n::plugin::TraitInfo {
-            vtable: std::mem::transmute(vtable),
+            vtable: std::mem::transmute::<
+                &gs11n::dynamic::VTable<dyn ToString>,
+                &gs11n::plugin::UnsafeVTable,
+            >(vtable),
             update_fn: sync_trait,
         };
         // hack, we need the trait's full name to be "dyn gs11n_cdylib_dynamic_test::ToString"
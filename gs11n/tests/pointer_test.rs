@@ -0,0 +1,68 @@
+use gs11n::decoder::Decoder;
+use gs11n::encoder::Encoder;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[test]
+fn pointer_test() {
+    let name = String::from("orc");
+    let expect = Encoder::from(&name).encode();
+
+    assert_eq!(Encoder::from(&&name).encode(), expect);
+    assert_eq!(Encoder::from(&Rc::new(name.clone())).encode(), expect);
+    assert_eq!(Encoder::from(&Arc::new(name.clone())).encode(), expect);
+    assert_eq!(
+        Encoder::from(&name.clone().into_boxed_str()).encode(),
+        expect
+    );
+
+    let decoder = Decoder::from_data(expect.as_slice());
+    let decode_rc: Rc<String> = decoder.decode().unwrap();
+    assert_eq!(*decode_rc, name);
+    let decode_arc: Arc<String> = decoder.decode().unwrap();
+    assert_eq!(*decode_arc, name);
+    let decode_box: Box<str> = decoder.decode().unwrap();
+    assert_eq!(&*decode_box, name.as_str());
+
+    let mut assets: HashMap<u32, Arc<Vec<f32>>> = HashMap::default();
+    let mesh = Arc::new(vec![1.0f32, 2.0, 3.0]);
+    assets.insert(1, mesh.clone());
+    assets.insert(2, mesh);
+    let encoder = Encoder::from(&assets);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_assets: HashMap<u32, Vec<f32>> = decoder.decode().unwrap();
+    assert_eq!(decode_assets[&1], vec![1.0f32, 2.0, 3.0]);
+    assert_eq!(decode_assets[&2], vec![1.0f32, 2.0, 3.0]);
+}
+
+#[test]
+fn cow_test() {
+    let borrowed: Cow<'static, str> = Cow::Borrowed("goblin");
+    let owned: Cow<'static, str> = Cow::Owned(String::from("goblin"));
+    let expect = Encoder::from(&"goblin").encode();
+    assert_eq!(Encoder::from(&borrowed).encode(), expect);
+    assert_eq!(Encoder::from(&owned).encode(), expect);
+
+    let decoder = Decoder::from_data(expect.as_slice());
+    let decode_cow: Cow<'static, str> = decoder.decode().unwrap();
+    assert_eq!(decode_cow, borrowed);
+
+    let slice = [1u32, 2, 3];
+    let cow: Cow<[u32]> = Cow::Borrowed(&slice);
+    let encoder = Encoder::from(&cow);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, Encoder::from(&slice).encode());
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_cow: Cow<[u32]> = decoder.decode().unwrap();
+    assert_eq!(decode_cow, cow);
+    let decode_box: Box<[u32]> = decoder.decode().unwrap();
+    assert_eq!(&*decode_box, &slice);
+
+    let cow: Cow<f32> = Cow::Owned(1.5);
+    let encoder = Encoder::from(&cow);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, 1.5f32.to_le_bytes().to_vec());
+}
@@ -23,10 +23,10 @@ impl gs11n::Serialization for Foo {
     }
 }
 
-impl gs11n::DeSerialization for Foo {
+impl<'de> gs11n::DeSerialization<'de> for Foo {
     fn decode(
         ptr: &mut *const u8,
-        ctx: &gs11n::decoder::DecodeContext,
+        ctx: &gs11n::decoder::DecodeContext<'de>,
     ) -> Result<Self, gs11n::decoder::DecodeError> {
         use gs11n::decoder::decode_wired_id;
         use gs11n::wire_type::WireType;
@@ -63,10 +63,10 @@ impl gs11n::Serialization for FooPrefab {
     }
 }
 
-impl gs11n::DeSerialization for FooPrefab {
+impl<'de> gs11n::DeSerialization<'de> for FooPrefab {
     fn decode(
         ptr: &mut *const u8,
-        ctx: &gs11n::decoder::DecodeContext,
+        ctx: &gs11n::decoder::DecodeContext<'de>,
     ) -> Result<Self, gs11n::decoder::DecodeError> {
         use gs11n::decoder::decode_wired_id;
         use gs11n::wire_type::WireType;
@@ -132,3 +132,19 @@ fn prefab_test() {
     let foo = decoder.decode::<Foo>().unwrap();
     assert_eq!(foo.v, prefab_content);
 }
+
+#[test]
+fn context_test() {
+    let prefab_loader = TestPrefabLoader {
+        prefabs: FxHashMap::default(),
+    };
+    let data = [0b101_00000, 1];
+
+    // a context can be built without a decoder, e.g. to decode a part of some data
+    let ctx = DecodeContext::new(&data, &prefab_loader);
+    let mut ptr = data.as_ptr();
+    assert_eq!(
+        <Foo as gs11n::DeSerialization>::decode(&mut ptr, &ctx).err(),
+        Some(DecodeError::PrefabNotExist)
+    );
+}
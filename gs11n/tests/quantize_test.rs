@@ -0,0 +1,39 @@
+use gs11n::quantize::Quantizer;
+
+#[test]
+fn quantize_error_test() {
+    for &(min, max, bits) in &[(-512.0, 512.0, 16), (0.0, 360.0, 12), (-1.0, 1.0, 1)] {
+        let quantizer = Quantizer::new(min, max, bits);
+        assert_eq!(
+            quantizer.max_error(),
+            (max - min) / ((1u64 << bits) - 1) as f64 / 2.0
+        );
+        for i in 0..=10_000 {
+            let value = min + (max - min) * i as f64 / 10_000.0;
+            let quantized = quantizer.quantize(value);
+            assert!(u64::from(quantized) < 1 << bits);
+            let error = (quantizer.dequantize(quantized) - value).abs();
+            assert!(
+                error <= quantizer.max_error() + 1e-9,
+                "{} of {}",
+                error,
+                value
+            );
+        }
+        assert_eq!(quantizer.dequantize(quantizer.quantize(min)), min);
+        assert_eq!(quantizer.dequantize(quantizer.quantize(max)), max);
+    }
+}
+
+#[test]
+fn quantize_clamp_test() {
+    let quantizer = Quantizer::new(-512.0, 512.0, 16);
+    assert_eq!(quantizer.quantize(-1000.0), 0);
+    assert_eq!(quantizer.quantize(1000.0), 0xFFFF);
+    assert_eq!(quantizer.quantize(f64::NAN), 0);
+    assert_eq!(quantizer.quantize(f64::INFINITY), 0xFFFF);
+    assert_eq!(quantizer.dequantize(u32::MAX), 512.0);
+
+    let quantizer = Quantizer::new(0.0, 1.0, 32);
+    assert_eq!(quantizer.quantize(1.0), u32::MAX);
+}
@@ -0,0 +1,50 @@
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo};
+
+#[test]
+fn result_test() {
+    let outcomes: Vec<Result<u32, String>> = vec![Ok(300), Err(String::from("timeout"))];
+    let encoder = Encoder::from(&outcomes);
+    let encode_result = encoder.encode();
+    let mut expect = vec![2, 0, 0xAC, 0x2, 1, 7];
+    expect.extend_from_slice(b"timeout");
+    assert_eq!(encode_result, expect);
+
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(
+        decoder.decode::<Vec<Result<u32, String>>>().unwrap(),
+        outcomes
+    );
+
+    let decoder = Decoder::from_data(&[2, 0]);
+    assert_eq!(
+        decoder.decode::<Result<u32, u32>>(),
+        Err(DecodeError::InvalidType)
+    );
+}
+
+#[test]
+fn range_test() {
+    let spawn = 3u32..=200;
+    let encoder = Encoder::from(&spawn);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![3, 0xC8, 0x1]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<RangeInclusive<u32>>().unwrap(), spawn);
+
+    let ranges = vec![0.0f32..1.0, -1.0..0.5];
+    let encoder = Encoder::from(&ranges);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result.len(), 1 + 8 * 2);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<Range<f32>>>().unwrap(), ranges);
+
+    let encoder = Encoder::from(&(5i32.., ..-5i32));
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![10, 9]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let (from, to): (RangeFrom<i32>, RangeTo<i32>) = decoder.decode().unwrap();
+    assert_eq!(from, 5..);
+    assert_eq!(to, ..-5);
+}
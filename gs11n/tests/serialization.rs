@@ -1,4 +1,4 @@
-use gs11n::decoder::Decoder;
+use gs11n::decoder::{DecodeError, Decoder};
 use gs11n::meta_data::Metadata;
 use gs11n::signed::{UnZigZag, ZigZag};
 use gs11n::swap_bytes::SwapBytes;
@@ -140,3 +140,19 @@ fn integer_test() {
     let r11 = char::decode(&mut ptr, decode_ctx).unwrap();
     assert_eq!(r11, n11);
 }
+
+#[test]
+fn corrupted_size_test() {
+    // sizes and counts near `usize::MAX` fail without computing an address out of the data
+    let huge_size = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F, b'a'];
+    let decoder = Decoder::from_data(&huge_size);
+    assert_eq!(
+        decoder.decode::<String>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+    let decoder = Decoder::from_data(&huge_size);
+    assert_eq!(
+        decoder.decode::<Option<u8>>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
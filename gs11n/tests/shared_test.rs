@@ -0,0 +1,180 @@
+use gs11n::decoder::{DecodeContext, DecodeError, Decoder};
+use gs11n::encoder::{EncodeError, Encoder};
+use gs11n::meta_data::Metadata;
+use gs11n::{DeSerialization, Serialization, WireTypeTrait};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+
+#[derive(Default)]
+struct Node {
+    name: String,
+    parent: RefCell<Weak<Node>>,
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+impl Node {
+    fn new(name: &str) -> Rc<Node> {
+        Rc::new(Node {
+            name: String::from(name),
+            ..Node::default()
+        })
+    }
+
+    fn add_child(self: &Rc<Self>, child: Rc<Node>) {
+        *child.parent.borrow_mut() = Rc::downgrade(self);
+        self.children.borrow_mut().push(child);
+    }
+}
+
+impl WireTypeTrait for Node {}
+
+impl Serialization for Node {
+    fn encode(&self, ptr: &mut *mut u8, meta_data: &mut Metadata) {
+        self.name.encode(ptr, meta_data.get(0));
+        self.parent.borrow().encode(ptr, meta_data.get(1));
+        self.children.borrow().encode(ptr, meta_data.get(2));
+    }
+
+    fn record(&self, meta_data: &mut Metadata) {
+        self.name.record(meta_data.get(0));
+        self.parent.borrow().record(meta_data.get(1));
+        self.children.borrow().record(meta_data.get(2));
+        meta_data.size = meta_data.get(0).size + meta_data.get(1).size + meta_data.get(2).size;
+    }
+}
+
+impl<'de> DeSerialization<'de> for Node {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
+        Ok(Node {
+            name: String::decode(ptr, ctx)?,
+            parent: RefCell::new(Weak::decode(ptr, ctx)?),
+            children: RefCell::new(Vec::decode(ptr, ctx)?),
+        })
+    }
+}
+
+#[test]
+fn shared_graph_test() {
+    let root = Node::new("root");
+    let shared = Node::new("shared");
+    root.add_child(Node::new("first"));
+    root.add_child(shared.clone());
+    root.add_child(shared);
+
+    let encoder = Encoder::from_shared(&root);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, encoder.encode());
+
+    let decoder = Decoder::from_data(encode_result.as_slice()).with_shared_references();
+    let decode_root: Rc<Node> = decoder.decode().unwrap();
+    assert_eq!(decode_root.name, "root");
+    assert!(decode_root.parent.borrow().upgrade().is_none());
+
+    let children = decode_root.children.borrow();
+    assert_eq!(children.len(), 3);
+    assert_eq!(children[0].name, "first");
+    assert_eq!(children[1].name, "shared");
+    assert!(Rc::ptr_eq(&children[1], &children[2]));
+    assert!(!Rc::ptr_eq(&children[0], &children[1]));
+    for child in children.iter() {
+        let parent = child.parent.borrow().upgrade().unwrap();
+        assert!(Rc::ptr_eq(&parent, &decode_root));
+    }
+    // root is referenced by `decode_root` only, all the other references are weak
+    assert_eq!(Rc::strong_count(&decode_root), 1);
+    assert_eq!(Rc::strong_count(&children[1]), 2);
+}
+
+#[test]
+fn not_shared_test() {
+    let shared = Rc::new(String::from("shared"));
+    let value = vec![shared.clone(), shared];
+
+    // without shared references, shared values are encoded twice
+    let encoder = Encoder::from(&value);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decoded: Vec<Rc<String>> = decoder.decode().unwrap();
+    assert_eq!(decoded, value);
+    assert!(!Rc::ptr_eq(&decoded[0], &decoded[1]));
+
+    // shared references mode does not change values which are not shared
+    let value = vec![Arc::new(1.5f32), Arc::new(2.5f32)];
+    assert_eq!(
+        Encoder::from_shared(&value).encode(),
+        Encoder::from(&value).encode()
+    );
+
+    let decoder = Decoder::from_data(&[2]);
+    assert_eq!(
+        decoder.decode::<Weak<String>>().err(),
+        Some(DecodeError::InvalidReference)
+    );
+}
+
+#[test]
+fn shared_error_test() {
+    // a strong cycle can not be rebuilt
+    let root = Node::new("root");
+    root.children.borrow_mut().push(root.clone());
+    let encoder = Encoder::from_shared(&root);
+    let encode_result = encoder.encode();
+    root.children.borrow_mut().clear();
+    let decoder = Decoder::from_data(encode_result.as_slice()).with_shared_references();
+    assert_eq!(
+        decoder.decode::<Rc<Node>>().err(),
+        Some(DecodeError::ReferenceCycle)
+    );
+
+    // a reference to an allocation which is not met yet
+    let decoder = Decoder::from_data(&[2]).with_shared_references();
+    assert_eq!(
+        decoder.decode::<Arc<String>>().err(),
+        Some(DecodeError::InvalidReference)
+    );
+
+    // a reference to an allocation of another type
+    let value = (Rc::new(String::from("name")), Rc::new(String::from("name")));
+    let encoder = Encoder::from_shared(&value);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice()).with_shared_references();
+    assert!(decoder.decode::<(Rc<String>, Rc<String>)>().is_ok());
+    let value = (value.0.clone(), value.0);
+    let encoder = Encoder::from_shared(&value);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice()).with_shared_references();
+    assert_eq!(
+        decoder.decode::<(Rc<String>, Rc<Vec<u8>>)>().err(),
+        Some(DecodeError::InvalidReference)
+    );
+}
+
+#[test]
+fn weak_test() {
+    // a `Weak` can only point to a value in shared references mode
+    let root = Node::new("root");
+    root.add_child(Node::new("child"));
+    assert_eq!(
+        Encoder::from(&root).try_encode(),
+        Err(EncodeError::UnsharedWeak)
+    );
+
+    // and only to a value which is tracked
+    let value = Rc::new(5u32);
+    let pair = (value.clone(), Rc::downgrade(&value));
+    assert_eq!(
+        Encoder::from_shared(&pair).try_encode(),
+        Err(EncodeError::UntrackedWeak)
+    );
+    let decoder = Decoder::from_data(&[1, 1, 5]).with_shared_references();
+    assert_eq!(
+        decoder.decode::<Weak<u32>>().err(),
+        Some(DecodeError::InvalidReference)
+    );
+
+    // a `Weak` which can not be upgraded is null in both modes
+    let weak = Weak::<u32>::new();
+    assert_eq!(Encoder::from(&weak).try_encode(), Ok(vec![0]));
+    assert_eq!(Encoder::from_shared(&weak).try_encode(), Ok(vec![0]));
+}
@@ -56,8 +56,8 @@ impl Serialization for Foo {
     }
 }
 
-impl DeSerialization for Foo {
-    fn decode(ptr: &mut *const u8, ctx: &DecodeContext) -> Result<Self, DecodeError> {
+impl<'de> DeSerialization<'de> for Foo {
+    fn decode(ptr: &mut *const u8, ctx: &DecodeContext<'de>) -> Result<Self, DecodeError> {
         let mut v = Self::default();
         while (*ptr).lt(&ctx.bounds_checker.get_bound()) {
             let (id, wire_type) = decode_wired_id(ptr, ctx)?;
@@ -120,7 +120,10 @@ fn struct_serialization_test() {
         0b111_00100,
         0x5,
         0x4,
-        0xDB, 0xF, 0x49, 0x40,
+        0xDB,
+        0xF,
+        0x49,
+        0x40,
         // f_30
         0b110_11110,
         0x0,
@@ -0,0 +1,98 @@
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use gs11n::time::InstantOffset;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[test]
+fn duration_test() {
+    let cooldown = Duration::new(3, 500);
+    let encoder = Encoder::from(&cooldown);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![3, 0xF4, 0x3]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Duration>().unwrap(), cooldown);
+
+    let timers = vec![Duration::ZERO, Duration::from_millis(1500), Duration::MAX];
+    let encoder = Encoder::from(&timers);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<Duration>>().unwrap(), timers);
+
+    // 1_000_000_000 nanoseconds
+    let decoder = Decoder::from_data(&[0, 0x80, 0x94, 0xEB, 0xDC, 0x3]);
+    assert_eq!(
+        decoder.decode::<Duration>(),
+        Err(DecodeError::NanosOutOfRange)
+    );
+}
+
+#[test]
+fn system_time_test() {
+    let times = vec![
+        UNIX_EPOCH,
+        SystemTime::now(),
+        UNIX_EPOCH + Duration::new(1, 1),
+        UNIX_EPOCH - Duration::new(1, 0),
+        UNIX_EPOCH - Duration::from_millis(300),
+    ];
+    let encoder = Encoder::from(&times);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<SystemTime>>().unwrap(), times);
+
+    // -0.3s is encoded as -1s + 0.7s
+    let encoder = Encoder::from(&times[4]);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![1, 0x80, 0xCE, 0xE4, 0xCD, 0x2]);
+
+    let decoder = Decoder::from_data(&[2, 0x80, 0x94, 0xEB, 0xDC, 0x3]);
+    assert_eq!(
+        decoder.decode::<SystemTime>().err(),
+        Some(DecodeError::NanosOutOfRange)
+    );
+}
+
+#[test]
+fn instant_offset_test() {
+    let base = Instant::now();
+    let later = base + Duration::from_millis(2500);
+    let offsets = vec![
+        InstantOffset::new(later, base),
+        InstantOffset::new(base, later),
+        InstantOffset::new(base, base),
+    ];
+    let encoder = Encoder::from(&offsets);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_offsets: Vec<InstantOffset> = decoder.decode().unwrap();
+    assert_eq!(decode_offsets, offsets);
+
+    let new_base = base + Duration::from_secs(10);
+    assert_eq!(
+        decode_offsets[0].to_instant(new_base).unwrap(),
+        new_base + Duration::from_millis(2500)
+    );
+    assert_eq!(
+        decode_offsets[1].to_instant(new_base).unwrap(),
+        new_base - Duration::from_millis(2500)
+    );
+    assert_eq!(decode_offsets[2].to_instant(new_base).unwrap(), new_base);
+}
+
+#[test]
+fn max_offset_test() {
+    // -2^63 seconds is decoded as a duration of 2^63 seconds, which is out of the range of `i64`
+    let min_offset = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x1, 0];
+    let decoder = Decoder::from_data(&min_offset);
+    let offset: InstantOffset = decoder.decode().unwrap();
+    let encoder = Encoder::from(&offset);
+    assert_eq!(encoder.encode(), min_offset);
+
+    let max_offset = [
+        0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x1, 0xFF, 0x93, 0xEB, 0xDC, 0x3,
+    ];
+    let decoder = Decoder::from_data(&max_offset);
+    let offset: InstantOffset = decoder.decode().unwrap();
+    let encoder = Encoder::from(&offset);
+    assert_eq!(encoder.encode(), max_offset);
+}
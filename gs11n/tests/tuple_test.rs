@@ -0,0 +1,74 @@
+use gs11n::decoder::Decoder;
+use gs11n::encoder::Encoder;
+use gs11n::wire_type::WireType;
+use gs11n::WireTypeTrait;
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn tuple_test() {
+    let value = (1u32, 2.5f32, String::from("three"));
+    let encoder = Encoder::from(&value);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_value: (u32, f32, String) = decoder.decode().unwrap();
+    assert_eq!(decode_value, value);
+
+    let vec = vec![(1u32, 1.0f32), (200, 2.0), (30000, 3.0)];
+    let encoder = Encoder::from(&vec);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_vec: Vec<(u32, f32)> = decoder.decode().unwrap();
+    assert_eq!(decode_vec, vec);
+}
+
+#[test]
+fn tuple_key_test() {
+    let mut map: HashMap<(i32, i32), String> = HashMap::default();
+    map.insert((0, 0), String::from("grass"));
+    map.insert((-1, 2), String::from("water"));
+    let encoder = Encoder::from(&map);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_map: BTreeMap<(i32, i32), String> = decoder.decode().unwrap();
+    assert_eq!(decode_map.len(), 2);
+    assert_eq!(decode_map.get(&(0, 0)).unwrap(), "grass");
+    assert_eq!(decode_map.get(&(-1, 2)).unwrap(), "water");
+}
+
+fn bulk_copy<T: WireTypeTrait>() -> bool {
+    T::BULK_COPY
+}
+
+#[test]
+fn fixed_tuple_test() {
+    assert_eq!(<(f32, f32)>::WIRE_TYPE, WireType::Bits64);
+    assert_eq!(<(f64, f32, f32)>::WIRE_TYPE, WireType::Bits128);
+    assert_eq!(<(bool, bool)>::WIRE_TYPE, WireType::Bits16);
+    assert_eq!(<(f32, bool)>::WIRE_TYPE, WireType::LengthDelimited);
+    assert_eq!(<(u32, f32)>::WIRE_TYPE, WireType::LengthDelimited);
+
+    // the layout of a tuple is not specified, so it is encoded element by element
+    assert!(!bulk_copy::<(f32, f32, f32)>());
+    assert!(!bulk_copy::<(bool, bool)>());
+    assert!(!bulk_copy::<(u8, u8)>());
+
+    let vec = vec![(1.0f32, 2.0f32), (3.0, 4.0)];
+    let encoder = Encoder::from(&vec);
+    let encode_result = encoder.encode();
+    let mut expect = vec![2u8];
+    for v in [1.0f32, 2.0, 3.0, 4.0] {
+        expect.extend_from_slice(&v.to_le_bytes());
+    }
+    assert_eq!(encode_result, expect);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_vec: Vec<(f32, f32)> = decoder.decode().unwrap();
+    assert_eq!(decode_vec, vec);
+
+    let vec = vec![(true, false), (false, true)];
+    let encoder = Encoder::from(&vec);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![2, 1, 0, 0, 1]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let decode_vec: Vec<(bool, bool)> = decoder.decode().unwrap();
+    assert_eq!(decode_vec, vec);
+}
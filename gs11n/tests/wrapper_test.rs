@@ -0,0 +1,59 @@
+use gs11n::decoder::{DecodeError, Decoder};
+use gs11n::encoder::Encoder;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::num::{NonZeroI64, NonZeroU128, NonZeroU32, NonZeroU8, Wrapping};
+
+#[test]
+fn non_zero_test() {
+    let handles = vec![NonZeroU32::new(1).unwrap(), NonZeroU32::new(300).unwrap()];
+    let encoder = Encoder::from(&handles);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, Encoder::from(&vec![1u32, 300]).encode());
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<NonZeroU32>>().unwrap(), handles);
+
+    let handle = Some(NonZeroI64::new(-5).unwrap());
+    let encoder = Encoder::from(&handle);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Option<NonZeroI64>>().unwrap(), handle);
+
+    let id = NonZeroU128::new(u128::MAX).unwrap();
+    let encoder = Encoder::from(&id);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<NonZeroU128>().unwrap(), id);
+
+    let decoder = Decoder::from_data(&[0]);
+    assert_eq!(
+        decoder.decode::<NonZeroU8>(),
+        Err(DecodeError::InvalidNonZero)
+    );
+}
+
+#[test]
+fn wrapper_test() {
+    let counter = Wrapping(u8::MAX) + Wrapping(1);
+    let encoder = Encoder::from(&counter);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, vec![0]);
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Wrapping<u8>>().unwrap(), counter);
+
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse(3.5f32.to_bits()));
+    heap.push(Reverse(1.5f32.to_bits()));
+    let encoder = Encoder::from(&heap);
+    let encode_result = encoder.encode();
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    let mut decode_heap: BinaryHeap<Reverse<u32>> = decoder.decode().unwrap();
+    assert_eq!(decode_heap.pop(), Some(Reverse(1.5f32.to_bits())));
+
+    let values = vec![Reverse(1.0f32), Reverse(-2.0f32)];
+    let encoder = Encoder::from(&values);
+    let encode_result = encoder.encode();
+    assert_eq!(encode_result, Encoder::from(&vec![1.0f32, -2.0]).encode());
+    let decoder = Decoder::from_data(encode_result.as_slice());
+    assert_eq!(decoder.decode::<Vec<Reverse<f32>>>().unwrap(), values);
+}
@@ -23,19 +23,28 @@
 //! let decoder = Decoder::from_data(buffer);
 //! let orc2: Orc = decoder.decode().unwrap();
 //! ```
+//! `encode` panics if the value can not be encoded, e.g. a path which is not valid UTF-8, use
+//! `Encoder::try_encode` to get an `EncodeError` instead.
 //!
-//! Notice that you struct type ***must*** implement trait `Default` for now, this restriction may be relaxed in the future.
+//! A field which is missing in the data is an error `DecodeError::MissingField(id)`, unless it is declared with
+//! `#[serialized(id, default)]`, then it is `Default::default()`, or `#[serialized(id, default = expr)]` and
+//! `#[serialized(id, default = "path::to_fn")]` for other values. Fields without `#[serialized]` are always
+//! `Default::default()`, so your type does not need to implement trait `Default`.
+//! A struct which implements `Default` can be declared with `#[struct_default]`, then missing fields and fields
+//! without `#[serialized]` take their values from `Self::default()` instead, and arrays of it can be padded.
 //!
 //! GS11N use some ideas from Protobuf, which are:
-//! 1. Every field of a struct or enum can (not must) have an ID, which makes different versions of your types compatible.
+//! 1. Every field of a struct or enum can (not must) have an ID, which makes different versions of your types
+//!    compatible: fields can be added or removed, and a field which is missing in old data needs a
+//!    `default`, or a `#[struct_default]` struct, as described above.
 //! 2. Use varint encoding to reduce the size of the serialization.
 //!
 //! Other features are:
 //! 1. You can serialize a `dyn` type, but need to give the type an type ID (check tests for a example)
 //! 2. Your `dyn` types can be compiled into a dynamic library, and load it later, this can be useful when debugging
-//! or code updating (for example a hot fix or a DLC).
+//!    or code updating (for example a hot fix or a DLC).
 //! 3. Prefab Loader: you can offer a prefab loader when create a decoder, in which you can
-//! bind GS11N to your resources system.
+//!    bind GS11N to your resources system.
 //! 4. You can use to serialize a type as a whole, usually some common types, to simplify the code:
 //! ```ignore
 //! #[derive(PartialEq, Debug, GS11N, Default)]
@@ -47,6 +56,60 @@
 //! };
 //! ```
 //! notice that is you choose to do this, the encoded date will not compatible if fields are added or removed
+//! 5. Strings `&str` and bytes `&[u8]` can be borrowed from the encoded data instead of being copied,
+//!    as long as the data outlives the decoded value:
+//! ```ignore
+//! #[derive(GS11N, Default)]
+//! struct Dialog<'a> {
+//!   #[serialized(0)]
+//!   speaker: &'a str,
+//!   #[serialized(1)]
+//!   voice: &'a [u8],
+//! }
+//! ```
+//! a borrowed `&[u8]` is encoded as its raw bytes, while `Vec<u8>`, `Box<[u8]>` and `[u8; N]` are
+//! sequences of varints, they are not borrowable and can not decode the data of a `&[u8]`. Slices of
+//! other types, like `&[u32]`, are not borrowable either. A generic type which can not borrow from
+//! the data should be bounded by `DeSerializationOwned` instead
+//! 6. Enable feature `glam` or `mint` of `gs11n` to serialize their vectors, quaternions and matrices
+//!    as blocks of floats, and feature `half` for half precision floats `f16` and `bf16`, which take
+//!    2 bytes. Features `smallvec`, `arrayvec` and `indexmap` add their containers, which are encoded
+//!    the same as `Vec`, `HashSet` and `HashMap`.
+//! 7. Float fields can be quantized into integers of fewer bits, values are clamped into the range,
+//!    and the error is at most half a step, `(max - min) / (2^bits - 1) / 2`:
+//! ```ignore
+//! #[serialized(2, quantize(min = -512.0, max = 512.0, bits = 16))]
+//! x: f32,
+//! ```
+//! 8. `PathBuf` and `OsString` are encoded as UTF-8 strings, a path which is not valid UTF-8 can not
+//!    be encoded, `Encoder::try_encode` returns `EncodeError::InvalidUtf8` for it, and `Encoder::encode`
+//!    panics. `IpAddr` and `SocketAddr` are encoded as a tag byte followed by the 4 or 16 octets of the address.
+//! 9. The length of an array field can be changed between versions, decode old data with
+//!    `Decoder::with_array_length_policy(ArrayLengthPolicy::Truncate)` or `ArrayLengthPolicy::Pad`.
+//!    Padding fills the missing elements with empty values, like `0`, `""` or an empty `Vec`. A
+//!    derived type can only be padded if it is declared with `#[struct_default]`, its padding is
+//!    `Self::default()`, other derived types and types without an empty value, like `NonZeroU32`,
+//!    fail with `DecodeError::ArrayLengthMismatch`.
+//! 10. Tuple structs like `struct Meters(#[serialized(0)] f32);` are supported, their fields are numbered by
+//!     positions if `#[compact]`. A unit struct is encoded as a zero-size value.
+//! 11. A variant without `#[serialized(id)]` uses its discriminant as the id, a unit variant is encoded as the
+//!     id only, and an enum without any fields, like `enum Faction { Red, Blue }`, is encoded as a varint.
+//! 12. Fields of variants like `Move { x: f32, y: f32 }` and `Hit(EntityId, u32)` are encoded like the fields of a
+//!     struct, prefixed by their size, they are numbered by positions unless given `#[serialized(id)]`, so fields can
+//!     still be added later.
+//! 13. Variants of an enum with `#[length_delimited]` are prefixed by their sizes, so a reader can keep the
+//!     variants it does not know in a variant with `#[serialized(other)]`, which holds the id and the data, and
+//!     writes them back unchanged:
+//! ```ignore
+//! #[derive(GS11N)]
+//! #[length_delimited]
+//! enum Message {
+//!   Ping,
+//!   Chat(String),
+//!   #[serialized(other)]
+//!   Unknown(usize, Vec<u8>),
+//! }
+//! ```
 //!
 //! if you do not want to generate serialization or deserialization code:
 //! ```no_ignore
@@ -77,7 +140,14 @@ use syn::{parse_macro_input, DeriveInput};
 
 #[proc_macro_derive(
     GS11N,
-    attributes(serialized, compact, no_serialization, no_deserialization)
+    attributes(
+        serialized,
+        compact,
+        no_serialization,
+        no_deserialization,
+        length_delimited,
+        struct_default
+    )
 )]
 pub fn s11n_macro_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
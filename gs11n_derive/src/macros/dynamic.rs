@@ -27,18 +27,12 @@ pub fn expand_trait(mut trait_input: ItemTrait) -> TokenStream {
     });
 
     let trait_name = &trait_input.ident;
-    let const_name = syn::Ident::new(
-        &format!(
-            "_DYN_METADATA_FOR_{}",
-            trait_name.to_string().to_case(Case::UpperSnake)
-        ),
-        trait_name.span(),
-    );
     // TODO check crate type, avoid dereference if not a dylib
     // https://github.com/rust-lang/rust/issues/20267
-    return quote! {
+    quote! {
         #trait_input
-        const #const_name: () = {
+        // anonymous const, so impls inside it are not considered as non-local definitions
+        const _: () = {
             lazy_static::lazy_static! {
                 static ref VTABLE : gs11n::dynamic::VTable<dyn #trait_name> =  std::sync::RwLock::new(rustc_hash::FxHashMap::default());
             }
@@ -51,6 +45,8 @@ pub fn expand_trait(mut trait_input: ItemTrait) -> TokenStream {
             }
             impl gs11n::WireTypeTrait for Box<dyn #trait_name> {
                 const WIRE_TYPE: gs11n::serialization::wire_type::WireType = gs11n::serialization::wire_type::WireType::LengthDelimited;
+                // the value may be a struct, which is read until the end of its data
+                const SELF_DELIMITED: bool = false;
             }
             impl gs11n::Serialization for Box<dyn #trait_name> {
                 fn encode(&self, ptr: &mut *mut u8, meta_data: &mut gs11n::meta_data::Metadata) {
@@ -63,8 +59,8 @@ pub fn expand_trait(mut trait_input: ItemTrait) -> TokenStream {
                     self.dyn_record(meta_data);
                 }
             }
-            impl gs11n::DeSerialization for Box<dyn #trait_name> {
-                fn decode(ptr: &mut *const u8, ctx: &gs11n::decoder::DecodeContext) -> Result<Self, gs11n::decoder::DecodeError> {
+            impl<'de> gs11n::DeSerialization<'de> for Box<dyn #trait_name> {
+                fn decode(ptr: &mut *const u8, ctx: &gs11n::decoder::DecodeContext<'de>) -> Result<Self, gs11n::decoder::DecodeError> {
                     // read type id
                     let id = usize::decode(ptr, ctx)?;
                     let v_table = REF_VTABLE.load(std::sync::atomic::Ordering::Relaxed);
@@ -113,7 +109,7 @@ pub fn expand_trait(mut trait_input: ItemTrait) -> TokenStream {
                 register.insert(String::from(std::any::type_name::<dyn #trait_name>()), trait_info);
             }
         };
-    };
+    }
 }
 
 pub fn expand_impl(mut impl_input: ItemImpl) -> TokenStream {
@@ -162,7 +158,7 @@ pub fn expand_impl(mut impl_input: ItemImpl) -> TokenStream {
         impl_input.span(),
     );
 
-    return quote! {
+    quote! {
         #impl_input
         #[ctor::ctor]
         fn #fn_name () {
@@ -172,7 +168,7 @@ pub fn expand_impl(mut impl_input: ItemImpl) -> TokenStream {
                 Ok(Box::new(v))
             })
         }
-    };
+    }
 }
 
 pub enum Input {
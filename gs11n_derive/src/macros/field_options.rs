@@ -0,0 +1,121 @@
+use proc_macro2::TokenStream;
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{parenthesized, Expr, Ident, Lit, LitInt, LitStr, Path, Token, UnOp};
+
+/// Options after the id in `#[serialized(id, ...)]`.
+#[derive(Default)]
+pub struct FieldOptions {
+    pub quantize: Option<Quantize>,
+    /// The value of the field if it is missing, instead of an error.
+    pub default: Option<FieldDefault>,
+}
+
+pub enum FieldDefault {
+    /// `default`, use `Default::default()`.
+    Trait,
+    /// `default = expr`
+    Expr(Expr),
+    /// `default = "path::to_fn"`, call the function.
+    Function(Path),
+}
+
+/// `quantize(min = .., max = .., bits = ..)`, encode a float field as an integer in the range.
+pub struct Quantize {
+    pub min: Expr,
+    pub max: Expr,
+    pub bits: LitInt,
+}
+
+impl Quantize {
+    /// The range `(min, max)`, if both of them are number literals.
+    pub fn literal_range(&self) -> Option<(f64, f64)> {
+        Some((literal_value(&self.min)?, literal_value(&self.max)?))
+    }
+}
+
+/// The value of a number literal like `1`, `-0.5` or `(2.0)`.
+fn literal_value(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Float(float) => float.base10_parse().ok(),
+            Lit::Int(int) => int.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+            literal_value(&unary.expr).map(|value| -value)
+        }
+        Expr::Paren(paren) => literal_value(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Split the arguments of `#[serialized(...)]` into the id and the rest options.
+pub fn parse_id(input: ParseStream) -> Result<(LitInt, TokenStream)> {
+    let id = input.parse()?;
+    let options = input.parse()?;
+    Ok((id, options))
+}
+
+impl Parse for FieldOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = FieldOptions::default();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name: Ident = input.parse()?;
+            if name == "quantize" && options.quantize.is_none() {
+                options.quantize = Some(input.parse()?);
+            } else if name == "default" && options.default.is_none() {
+                options.default = Some(input.parse()?);
+            } else {
+                return Err(input.error("unrecognized option"));
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl Parse for FieldDefault {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if !input.peek(Token![=]) {
+            return Ok(FieldDefault::Trait);
+        }
+        input.parse::<Token![=]>()?;
+        if input.peek(LitStr) {
+            let path: LitStr = input.parse()?;
+            Ok(FieldDefault::Function(path.parse()?))
+        } else {
+            Ok(FieldDefault::Expr(input.parse()?))
+        }
+    }
+}
+
+impl Parse for Quantize {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let (mut min, mut max, mut bits) = (None, None, None);
+        while !content.is_empty() {
+            let name: Ident = content.parse()?;
+            content.parse::<Token![=]>()?;
+            if name == "min" && min.is_none() {
+                min = Some(content.parse()?);
+            } else if name == "max" && max.is_none() {
+                max = Some(content.parse()?);
+            } else if name == "bits" && bits.is_none() {
+                bits = Some(content.parse()?);
+            } else {
+                return Err(content.error("unrecognized quantize argument"));
+            }
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        match (min, max, bits) {
+            (Some(min), Some(max), Some(bits)) => Ok(Quantize { min, max, bits }),
+            _ => Err(content.error("missing quantize argument")),
+        }
+    }
+}
@@ -1,17 +1,24 @@
 pub mod dynamic;
+pub mod field_options;
 pub mod serialization;
 
+use crate::field_options::{parse_id, FieldOptions};
 use proc_macro2::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::{quote, quote_spanned};
 use std::collections::BTreeMap;
 use syn::spanned::Spanned;
-use syn::{Attribute, Data, DeriveInput, Field, Generics, Type, Variant};
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Field, Fields, Generics, Index, Lit, Member, Type, Variant,
+};
 
 pub(crate) static KEYWORD_ATTR_NAME: &str = "serialized";
 pub(crate) static KEYWORD_COMPACT: &str = "compact";
 pub(crate) static KEYWORD_NO_SERIALIZATION: &str = "no_serialization";
 pub(crate) static KEYWORD_NO_DESERIALIZATION: &str = "no_deserialization";
+pub(crate) static KEYWORD_LENGTH_DELIMITED: &str = "length_delimited";
+pub(crate) static KEYWORD_OTHER: &str = "other";
+pub(crate) static KEYWORD_STRUCT_DEFAULT: &str = "struct_default";
 
 struct Error {
     pub span: Span,
@@ -19,8 +26,30 @@ struct Error {
 }
 
 pub struct SerializableField<'a> {
-    name: &'a Ident,
-    ty: &'a Type,
+    /// The field of a struct, or the name of a variant.
+    member: Member,
+    /// `None` for a unit variant.
+    ty: Option<&'a Type>,
+    options: FieldOptions,
+    /// Fields of a struct-like or multi-field variant, which have their own ids.
+    variant_fields: Option<BTreeMap<usize, SerializableField<'a>>>,
+}
+
+/// Fields of a tuple struct or variant are named by their positions.
+pub(crate) fn member_of(index: usize, field: &Field) -> Member {
+    match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    }
+}
+
+/// `#[serialized(other)]`
+fn is_other_attr(attr: &Attribute) -> bool {
+    attr.path.is_ident(KEYWORD_ATTR_NAME)
+        && attr
+            .parse_args::<Ident>()
+            .map(|ident| ident == KEYWORD_OTHER)
+            .unwrap_or(false)
 }
 
 pub struct Builder<'a> {
@@ -30,28 +59,37 @@ pub struct Builder<'a> {
     generics: &'a Generics,
     input_data: &'a Data,
     fields: BTreeMap<usize, SerializableField<'a>>,
+    /// Fields of a variant are being handled.
+    in_variant: bool,
+    /// Each variant of an enum is prefixed by its size, so unknown variants can be skipped.
+    length_delimited: bool,
+    /// The variant which holds the id and data of unknown variants.
+    other: Option<&'a Variant>,
+    /// Missing fields of a struct take their values from `Self::default()`.
+    struct_default: bool,
     no_ser: bool,
     no_de: bool,
 }
 
 impl<'a> Builder<'a> {
-    pub fn from(input: &DeriveInput) -> Builder {
+    pub fn from(input: &DeriveInput) -> Builder<'_> {
         let name = &input.ident;
         let mut compact: bool = false;
         let mut no_ser: bool = false;
         let mut no_de: bool = false;
+        let mut length_delimited: bool = false;
+        let mut struct_default: bool = false;
         for attr in &input.attrs {
             if attr.path.is_ident(KEYWORD_COMPACT) {
                 compact = true;
-                break;
-            }
-            if attr.path.is_ident(KEYWORD_NO_SERIALIZATION) {
+            } else if attr.path.is_ident(KEYWORD_NO_SERIALIZATION) {
                 no_ser = true;
-                break;
-            }
-            if attr.path.is_ident(KEYWORD_NO_DESERIALIZATION) {
+            } else if attr.path.is_ident(KEYWORD_NO_DESERIALIZATION) {
                 no_de = true;
-                break;
+            } else if attr.path.is_ident(KEYWORD_LENGTH_DELIMITED) {
+                length_delimited = true;
+            } else if attr.path.is_ident(KEYWORD_STRUCT_DEFAULT) {
+                struct_default = true;
             }
         }
 
@@ -62,25 +100,51 @@ impl<'a> Builder<'a> {
             generics: &input.generics,
             fields: BTreeMap::<usize, SerializableField>::default(),
             input_data: &input.data,
+            in_variant: false,
+            length_delimited,
+            other: None,
+            struct_default,
             no_ser,
             no_de,
         };
 
         match &input.data {
             Data::Struct(struct_data) => {
-                for field in &struct_data.fields {
-                    builder.handle_field(field);
+                if builder.length_delimited {
+                    builder.add_error(
+                        builder.name.span(),
+                        "length_delimited attribute is only supported on enums",
+                    );
+                }
+                for (index, field) in struct_data.fields.iter().enumerate() {
+                    builder.handle_field(index, field);
                 }
             }
             Data::Enum(enum_data) => {
+                if builder.struct_default {
+                    builder.add_error(
+                        builder.name.span(),
+                        "struct_default attribute is only supported on structs",
+                    );
+                }
                 if builder.compact {
                     builder.add_error(
                         builder.name.span(),
                         "compact attribute on enum makes no sense",
                     );
                 } else {
+                    // the same as the discriminants of rust, unless given explicitly
+                    let mut discriminant = Some(0);
                     for variant in &enum_data.variants {
-                        builder.handle_variant(variant);
+                        discriminant = builder.handle_variant(variant, discriminant);
+                    }
+                    if let Some(other) = builder.other {
+                        if !builder.length_delimited {
+                            builder.add_error(
+                                other.span(),
+                                "other variant requires `#[length_delimited]` on the enum",
+                            );
+                        }
                     }
                 }
             }
@@ -96,7 +160,30 @@ impl<'a> Builder<'a> {
         self.errors.push(Error { span, msg });
     }
 
-    fn handle_attrs(&mut self, attrs: &[Attribute], name: &'a Ident, ty: &'a Type) {
+    fn insert_field(
+        &mut self,
+        id: usize,
+        field: SerializableField<'a>,
+        span: Span,
+    ) -> Option<usize> {
+        if let std::collections::btree_map::Entry::Vacant(e) = self.fields.entry(id) {
+            e.insert(field);
+            Some(id)
+        } else {
+            self.add_error(span, "field id already used.");
+            None
+        }
+    }
+
+    /// `default_id` is used if no id is given by attribute, returns the id of the inserted field.
+    fn handle_attrs(
+        &mut self,
+        attrs: &[Attribute],
+        member: Member,
+        ty: Option<&'a Type>,
+        default_id: Option<usize>,
+    ) -> Option<usize> {
+        let mut inserted = None;
         let mut got_attr = false;
         for attr in attrs {
             if !attr.path.is_ident(KEYWORD_ATTR_NAME) {
@@ -106,7 +193,7 @@ impl<'a> Builder<'a> {
             if self.compact {
                 // TODO better log
                 self.add_error(attr.span(), "compact types cannot have attribute, if you want to add an index, use un-packed type");
-                return;
+                return None;
             }
 
             if got_attr {
@@ -116,75 +203,192 @@ impl<'a> Builder<'a> {
                 got_attr = true;
             }
 
-            match attr.parse_args::<syn::LitInt>() {
-                Ok(lit_int) => match lit_int.base10_parse() {
-                    Ok(k) => {
-                        if let std::collections::btree_map::Entry::Vacant(e) = self.fields.entry(k)
-                        {
-                            let serializable_field = SerializableField { name, ty };
-                            e.insert(serializable_field);
-                        } else {
-                            self.add_error(attr.span(), "field id already used.");
-                        }
+            let (lit_int, options) = match attr.parse_args_with(parse_id) {
+                Ok(args) => args,
+                Err(err) => {
+                    self.add_error(err.span(), "not a int literal");
+                    continue;
+                }
+            };
+            let options = match syn::parse2::<FieldOptions>(options) {
+                Ok(options) => options,
+                Err(err) => {
+                    self.add_error(
+                        err.span(),
+                        "unrecognized option, expect `default`, `default = ..` or `quantize(min = .., max = .., bits = ..)`",
+                    );
+                    continue;
+                }
+            };
+            if options.default.is_some()
+                && matches!(self.input_data, Data::Enum(_))
+                && !self.in_variant
+            {
+                self.add_error(attr.span(), "default is only supported on fields");
+                continue;
+            }
+            if let Some(quantize) = &options.quantize {
+                if matches!(self.input_data, Data::Enum(_)) && !self.in_variant {
+                    self.add_error(attr.span(), "quantize is only supported on struct fields");
+                    continue;
+                }
+                match quantize.bits.base10_parse::<u32>() {
+                    Ok(1..=32) => {}
+                    _ => {
+                        self.add_error(quantize.bits.span(), "quantize bits must be in 1..=32");
+                        continue;
                     }
-                    Err(err) => {
-                        self.add_error(err.span(), "cannot parse to decimal");
+                }
+                if let Some((min, max)) = quantize.literal_range() {
+                    if min >= max {
+                        self.add_error(attr.span(), "quantize range must have min < max");
+                        continue;
                     }
-                },
+                }
+            }
+
+            match lit_int.base10_parse() {
+                Ok(k) => {
+                    let serializable_field = SerializableField {
+                        member: member.clone(),
+                        ty,
+                        options,
+                        variant_fields: None,
+                    };
+                    inserted = self.insert_field(k, serializable_field, attr.span());
+                }
                 Err(err) => {
-                    self.add_error(err.span(), "not a int literal");
+                    self.add_error(err.span(), "cannot parse to decimal");
                 }
             };
         }
 
-        if self.compact && !got_attr {
-            let index = self.fields.len();
-            self.fields.insert(index, SerializableField { name, ty });
+        if got_attr {
+            return inserted;
         }
+        // compact fields are numbered by positions
+        let default_id = if self.compact {
+            Some(self.fields.len())
+        } else {
+            default_id
+        };
+        let id = default_id?;
+        let span = member.span();
+        let field = SerializableField {
+            member,
+            ty,
+            options: FieldOptions::default(),
+            variant_fields: None,
+        };
+        self.insert_field(id, field, span)
     }
 
-    fn handle_variant(&mut self, variant: &'a Variant) {
-        let name = &variant.ident;
-        let ty = if variant.fields.len() > 1 {
-            self.errors.push(Error {
-                span: variant.span(),
-                msg: "multiple types are not supported, consider capsule them in a struct type",
-            });
-            return;
-        } else if variant.fields.is_empty() {
-            self.errors.push(Error {
-                span: variant.span(),
-                msg: "WTF: no fields in a variant",
-            });
-            return;
-        } else {
-            &variant.fields.iter().last().unwrap().ty
+    /// Returns the discriminant of the next variant.
+    fn handle_variant(
+        &mut self,
+        variant: &'a Variant,
+        discriminant: Option<usize>,
+    ) -> Option<usize> {
+        let discriminant = match &variant.discriminant {
+            Some((_, Expr::Lit(expr))) => match &expr.lit {
+                Lit::Int(lit_int) => lit_int.base10_parse().ok(),
+                _ => None,
+            },
+            Some(_) => None,
+            None => discriminant,
+        };
+        let next = discriminant.and_then(|discriminant| discriminant.checked_add(1));
+
+        if variant.attrs.iter().any(is_other_attr) {
+            if self.other.is_some() {
+                self.add_error(variant.span(), "other variant already declared");
+            } else if variant.fields.len() != 2 {
+                self.add_error(
+                    variant.span(),
+                    "other variant must have two fields, the id and the data",
+                );
+            } else {
+                self.other = Some(variant);
+            }
+            return next;
+        }
+
+        // a single unnamed field is encoded as the value of the variant, other fields are encoded
+        // like a struct
+        let (ty, variant_fields) = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                (Some(&fields.unnamed[0].ty), None)
+            }
+            fields if fields.is_empty() => (None, None),
+            fields => (None, Some(self.handle_variant_fields(fields))),
         };
 
-        self.handle_attrs(&variant.attrs, name, ty);
+        let has_attr = variant
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident(KEYWORD_ATTR_NAME));
+        if !has_attr && discriminant.is_none() {
+            self.add_error(
+                variant.span(),
+                "variant id unknown, add `#[serialized(id)]` or an int literal discriminant",
+            );
+            return next;
+        }
+        let member = Member::Named(variant.ident.clone());
+        if let Some(id) = self.handle_attrs(&variant.attrs, member, ty, discriminant) {
+            self.fields.get_mut(&id).unwrap().variant_fields = variant_fields;
+        }
+        next
     }
 
-    fn handle_field(&mut self, field: &'a Field) {
-        let name: &Ident;
-        if let Option::Some(ident) = &field.ident {
-            name = ident;
-        } else {
-            return;
+    /// Fields of a variant are numbered by positions, unless given `#[serialized(id)]`.
+    fn handle_variant_fields(
+        &mut self,
+        fields: &'a Fields,
+    ) -> BTreeMap<usize, SerializableField<'a>> {
+        let enum_fields = std::mem::take(&mut self.fields);
+        self.in_variant = true;
+        for (index, field) in fields.iter().enumerate() {
+            self.handle_attrs(
+                &field.attrs,
+                member_of(index, field),
+                Some(&field.ty),
+                Some(index),
+            );
         }
-        let ty = &field.ty;
-        self.handle_attrs(&field.attrs, name, ty);
+        self.in_variant = false;
+        std::mem::replace(&mut self.fields, enum_fields)
+    }
+
+    /// A fieldless enum, like `enum Faction { Red, Blue }`, is encoded as a varint.
+    fn is_fieldless(&self) -> bool {
+        matches!(self.input_data, Data::Enum(_))
+            && !self.length_delimited
+            && self
+                .fields
+                .values()
+                .all(|field| field.ty.is_none() && field.variant_fields.is_none())
+    }
+
+    fn handle_field(&mut self, index: usize, field: &'a Field) {
+        self.handle_attrs(&field.attrs, member_of(index, field), Some(&field.ty), None);
+    }
+
+    /// A struct without fields, like `struct Marker;`, is encoded as a zero-size value.
+    fn is_unit(&self) -> bool {
+        matches!(self.input_data, Data::Struct(struct_data) if struct_data.fields.is_empty())
     }
 
     pub fn build(&mut self) -> TokenStream {
-        if self.fields.is_empty() {
+        if self.fields.is_empty() && !self.is_unit() {
             self.add_error(self.name.span(), "no serializable field found");
         }
 
         if self.errors.is_empty() {
             let gen = vec![self.build_serialization()];
-            return quote! {
+            quote! {
                 #(#gen)*
-            };
+            }
         } else {
             let errors = self.errors.iter().map(|error| {
                 let msg = error.msg;
@@ -1,11 +1,21 @@
-use crate::Builder;
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Data, Index};
+use crate::field_options::FieldDefault;
+use crate::{member_of, Builder, SerializableField};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+use syn::{Data, GenericParam, Index, Lifetime, LifetimeDef, Member};
 
 impl<'a> Builder<'a> {
     pub(crate) fn build_serialization(&self) -> TokenStream {
         let (encode_statements, record_statements, decode_statements) = match self.input_data {
+            Data::Struct(_) if self.is_unit() => (
+                quote!(let _ = (ptr, meta_data);),
+                quote!(meta_data.size = 0;),
+                quote! {
+                    let _ = (ptr, ctx);
+                    Result::Ok(Self {})
+                },
+            ),
             Data::Struct(_) => self.get_struct_statements(),
             Data::Enum(_) => self.get_enum_statements(),
             Data::Union(_) => {
@@ -14,10 +24,27 @@ impl<'a> Builder<'a> {
         };
         let name = self.name;
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let mut generated = Vec::with_capacity(3);
+        let wire_type = if self.is_fieldless() {
+            quote!(Varint)
+        } else {
+            quote!(LengthDelimited)
+        };
+        // fields of a struct are read until the end of its data
+        let self_delimited =
+            !matches!(self.input_data, Data::Struct(_)) || self.compact || self.is_unit();
+        let mut generated = Vec::with_capacity(4);
+        let quantizers = self.get_quantizers();
+        if !quantizers.is_empty() {
+            generated.push(quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #(#quantizers)*
+                }
+            });
+        }
         generated.push(quote! {
             impl #impl_generics gs11n::WireTypeTrait for #name #ty_generics #where_clause {
-                const WIRE_TYPE: gs11n::serialization::wire_type::WireType = gs11n::serialization::wire_type::WireType::LengthDelimited;
+                const WIRE_TYPE: gs11n::serialization::wire_type::WireType = gs11n::serialization::wire_type::WireType::#wire_type;
+                const SELF_DELIMITED: bool = #self_delimited;
             }
         });
         if !self.no_ser {
@@ -34,17 +61,67 @@ impl<'a> Builder<'a> {
         }
 
         if !self.no_de {
+            // arrays of a type which implements `Default` can be padded
+            let padding = if self.struct_default {
+                quote! {
+                    fn padding() -> Option<Self> {
+                        Some(Default::default())
+                    }
+                }
+            } else {
+                quote!()
+            };
+            // borrowed fields are decoded from the data, so the data must outlive them
+            let mut de_lifetime = LifetimeDef::new(Lifetime::new("'de", Span::call_site()));
+            de_lifetime
+                .bounds
+                .extend(self.generics.lifetimes().map(|def| def.lifetime.clone()));
+            let mut de_generics = self.generics.clone();
+            de_generics
+                .params
+                .insert(0, GenericParam::Lifetime(de_lifetime));
+            let (de_impl_generics, _, _) = de_generics.split_for_impl();
             generated.push(quote! {
-                impl #impl_generics gs11n::DeSerialization for #name #ty_generics #where_clause {
-                    fn decode(ptr: &mut *const u8, ctx: &gs11n::serialization::decoder::DecodeContext) -> Result<Self, gs11n::decoder::DecodeError> {
+                impl #de_impl_generics gs11n::DeSerialization<'de> for #name #ty_generics #where_clause {
+                    fn decode(ptr: &mut *const u8, ctx: &gs11n::serialization::decoder::DecodeContext<'de>) -> Result<Self, gs11n::decoder::DecodeError> {
                         #decode_statements
                     }
+                    #padding
                 }
             });
         }
-        return quote! {
+        quote! {
             #(#generated)*
+        }
+    }
+
+    /// Quantizers of the quantized fields, they are associated constants of the type, so that they
+    /// are built once, and `min < max` is checked when compiling.
+    fn get_quantizers(&self) -> Vec<TokenStream> {
+        let mut quantizers = Vec::new();
+        let mut push = |variant: Option<&Member>, fields: &BTreeMap<usize, SerializableField>| {
+            for (id, field) in fields {
+                if let Some(quantize) = &field.options.quantize {
+                    let (min, max, bits) = (&quantize.min, &quantize.max, &quantize.bits);
+                    let quantizer = quantizer_of(variant, *id);
+                    quantizers.push(quote! {
+                        #[doc(hidden)]
+                        #[allow(dead_code)]
+                        const #quantizer: gs11n::quantize::Quantizer = {
+                            assert!(((#min) as f64) < ((#max) as f64), "gs11n: quantize range must have min < max");
+                            gs11n::quantize::Quantizer::new_unchecked((#min) as f64, (#max) as f64, #bits)
+                        };
+                    });
+                }
+            }
         };
+        push(None, &self.fields);
+        for variant in self.fields.values() {
+            if let Some(variant_fields) = &variant.variant_fields {
+                push(Some(&variant.member), variant_fields);
+            }
+        }
+        quantizers
     }
 
     fn get_enum_statements(&self) -> (TokenStream, TokenStream, TokenStream) {
@@ -55,29 +132,161 @@ impl<'a> Builder<'a> {
         let mut decode_enum_items: Vec<TokenStream> = Vec::with_capacity(self.fields.len());
 
         for (id, field) in &self.fields {
-            let element_ty = field.ty;
-            let element_name = field.name;
+            let element_name = &field.member;
+
+            // the data of a variant follows its id, and is prefixed by its size if length delimited,
+            // fields of a variant are decoded until the end of the data, so they are always prefixed
+            let prefixed = self.length_delimited || field.variant_fields.is_some();
+            let (size_prefix, mut payload_size) = if prefixed {
+                (
+                    quote! {
+                        use gs11n::unsigned::EncodeSize;
+                        meta_data.get(0).size.encode_raw(ptr);
+                    },
+                    quote!(meta_data.get(0).size.varint_size() + meta_data.get(0).size),
+                )
+            } else {
+                (quote!(), quote!(meta_data.get(0).size))
+            };
+            let (pattern, encode_payload, record_payload, decode_payload) =
+                if let Some(variant_fields) = &field.variant_fields {
+                    // fields of a variant are encoded like a struct
+                    let members: Vec<_> = variant_fields
+                        .values()
+                        .map(|field| field.member.clone())
+                        .collect();
+                    let names: Vec<_> = members.iter().map(binding_of).collect();
+                    let (encode_stmts, record_stmts, decode_stmts) = self.get_fields_statements(
+                        variant_fields,
+                        false,
+                        |member| {
+                            let binding = binding_of(member);
+                            quote!((*#binding))
+                        },
+                        quote!(Self::#element_name),
+                        &members,
+                        Some(element_name),
+                    );
+                    (
+                        quote!(#enum_name::#element_name { #(#members: #names),* }),
+                        quote! {
+                            let meta_data = meta_data.get(0);
+                            #encode_stmts
+                        },
+                        quote! {
+                            {
+                                let meta_data = meta_data.get(0);
+                                #record_stmts
+                            }
+                        },
+                        decode_stmts,
+                    )
+                } else if let Some(element_ty) = field.ty {
+                    let (encode_payload, decode_payload) = if self.length_delimited {
+                        (
+                            quote!(v.encode(ptr, meta_data.get(0));),
+                            quote!(<#element_ty>::decode(ptr, ctx)?),
+                        )
+                    } else {
+                        // the value may be followed by other data, e.g. in a sequence
+                        payload_size =
+                            quote!(gs11n::encoder::size_of_nested::<#element_ty>(meta_data.get(0)));
+                        (
+                            quote!(gs11n::encoder::encode_nested(v, ptr, meta_data.get(0));),
+                            quote!(gs11n::decoder::decode_nested::<#element_ty>(ptr, ctx)?),
+                        )
+                    };
+                    (
+                        quote!(#enum_name::#element_name(v)),
+                        encode_payload,
+                        quote!(v.record(meta_data.get(0));),
+                        quote! {
+                            let v = #decode_payload;
+                            Ok(Self::#element_name(v))
+                        },
+                    )
+                } else if self.length_delimited {
+                    (
+                        quote!(#enum_name::#element_name { .. }),
+                        quote!(),
+                        quote!(meta_data.get(0).size = 0;),
+                        quote!(Ok(Self::#element_name {})),
+                    )
+                } else {
+                    // a unit variant is encoded as its id only
+                    encode_enum_items.push(quote! {
+                        #enum_name::#element_name { .. } => #id.encode(ptr, meta_data),
+                    });
+                    record_enum_items.push(quote! {
+                        #enum_name::#element_name { .. } => #id.varint_size(),
+                    });
+                    decode_enum_items.push(quote! {
+                        #id => Ok(Self::#element_name {}),
+                    });
+                    continue;
+                };
 
             encode_enum_items.push(quote! {
-                #enum_name::#element_name(v) => {
-                            #id.encode(ptr, meta_data);
-                            v.encode(ptr, meta_data);
+                #pattern => {
+                    #id.encode(ptr, meta_data);
+                    #size_prefix
+                    #encode_payload
                 }
             });
 
             record_enum_items.push(quote! {
-                #enum_name::#element_name(v) => { v.record(meta_data.get(0)); }
+                #pattern => {
+                    #record_payload
+                    #id.varint_size() + #payload_size
+                }
             });
 
-            decode_enum_items.push(quote! {
-                #id => {
-                            let v = <#element_ty>::decode(ptr, ctx)?;
-                            Ok(Self::#element_name(v))
-                        }
+            decode_enum_items.push(if prefixed {
+                quote! {
+                    #id => gs11n::decoder::decode_length_delimited_with(ptr, ctx, |ptr, ctx| {
+                        #decode_payload
+                    }),
+                }
+            } else {
+                quote! {
+                    #id => {
+                        #decode_payload
+                    }
+                }
             });
         }
 
-        return (
+        // unknown variants are kept by the other variant, the data is written back as it is, it can
+        // be any type like `Vec<u8>`, which is `AsRef<[u8]>` and `From<&[u8]>`
+        let decode_unknown = if let Some(other) = self.other {
+            let other_name = &other.ident;
+            let mut members = other
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| member_of(i, f));
+            let (id_member, data_member) = (members.next().unwrap(), members.next().unwrap());
+            encode_enum_items.push(quote! {
+                #enum_name::#other_name { #id_member: id, #data_member: data } => {
+                    id.encode(ptr, meta_data);
+                    AsRef::<[u8]>::as_ref(data).encode(ptr, meta_data.get(0));
+                }
+            });
+            record_enum_items.push(quote! {
+                #enum_name::#other_name { #id_member: id, #data_member: data } => {
+                    AsRef::<[u8]>::as_ref(data).record(meta_data.get(0));
+                    id.varint_size() + meta_data.get(0).size
+                }
+            });
+            quote! {
+                let data = <&[u8] as gs11n::DeSerialization>::decode(ptr, ctx)?;
+                Ok(Self::#other_name { #id_member: id, #data_member: data.into() })
+            }
+        } else {
+            quote!(Err(gs11n::decoder::DecodeError::InvalidType))
+        };
+
+        (
             quote! {
                 match self {
                     #(#encode_enum_items)*
@@ -85,54 +294,110 @@ impl<'a> Builder<'a> {
             },
             quote! {
                 use gs11n::unsigned::EncodeSize;
-                match self {
+                meta_data.size = match self {
                    #(#record_enum_items)*
-                }
-                let child_size = meta_data.get(0).size;
-                meta_data.size = child_size.varint_size() + child_size;
+                };
             },
             quote! {
                 let id = usize::decode(ptr, ctx)?;
                 match id {
                     #(#decode_enum_items)*
                     _ => {
-                        Err(gs11n::decoder::DecodeError::InvalidType)
+                        #decode_unknown
                     }
                 }
             },
-        );
+        )
     }
 
     fn get_struct_statements(&self) -> (TokenStream, TokenStream, TokenStream) {
-        let mut encode_field_stmts = Vec::with_capacity(self.fields.len());
-        let mut record_stmts = Vec::with_capacity(self.fields.len());
-        let mut size_calculate_stmts = Vec::with_capacity(self.fields.len());
-        let mut decode_stmts = Vec::with_capacity(self.fields.len());
+        let members = match self.input_data {
+            Data::Struct(struct_data) => struct_data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| member_of(index, field))
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.get_fields_statements(
+            &self.fields,
+            self.compact,
+            |member| quote!(self.#member),
+            quote!(Self),
+            &members,
+            None,
+        )
+    }
 
-        for (id, field) in &self.fields {
+    /// Statements to encode, record and decode `fields` like a struct, a field is read from the
+    /// place `encode_place` when encoding. When decoding, each field is collected into a local
+    /// variable, then the value is built by `constructor` with all the `members`, members which are
+    /// not serialized are `Default::default()`.
+    fn get_fields_statements(
+        &self,
+        fields: &BTreeMap<usize, SerializableField>,
+        compact: bool,
+        encode_place: impl Fn(&Member) -> TokenStream,
+        constructor: TokenStream,
+        members: &[Member],
+        variant: Option<&Member>,
+    ) -> (TokenStream, TokenStream, TokenStream) {
+        let mut encode_field_stmts = Vec::with_capacity(fields.len());
+        let mut record_stmts = Vec::with_capacity(fields.len());
+        let mut size_calculate_stmts = Vec::with_capacity(fields.len());
+        let mut decode_locals = Vec::with_capacity(fields.len());
+        let mut decode_stmts = Vec::with_capacity(fields.len());
+
+        for (id, field) in fields {
             let id = Index::from(*id);
-            let field_name = field.name;
             let field_ty = field.ty;
+            let value = encode_place(&field.member);
+            let target = binding_of(&field.member);
+
+            if !compact {
+                decode_locals.push(quote! {
+                    let mut #target: Option<#field_ty> = None;
+                });
+            }
+
+            if field.options.quantize.is_some() {
+                let quantizer = quantizer_of(variant, id.index as usize);
+                let quantizer = quote!(Self::#quantizer);
+                record_stmts.push(quote! {
+                    #quantizer.quantize(#value as f64).record(meta_data.get(#id));
+                });
+                encode_field_stmts.push(quote! {
+                    gs11n::encoder::encode_field(#id, &#quantizer.quantize(#value as f64), ptr, meta_data.get(#id));
+                });
+                size_calculate_stmts.push(quote! {
+                    + gs11n::encoder::size_of_field::<u32>(#id, meta_data.get(#id))
+                });
+                decode_stmts.push(quote! {
+                    #id => #target = Some(#quantizer.dequantize(gs11n::decoder::decode_field(ptr, ctx, is_prefab)?) as #field_ty),
+                });
+                continue;
+            }
 
             record_stmts.push(quote! {
-                self.#field_name.record(meta_data.get(#id));
+                #value.record(meta_data.get(#id));
             });
 
-            if self.compact {
+            if compact {
                 encode_field_stmts.push(quote! {
-                    self.#field_name.encode(ptr, meta_data.get(#id));
+                    gs11n::encoder::encode_nested(&#value, ptr, meta_data.get(#id));
                 });
 
                 size_calculate_stmts.push(quote! {
-                    + meta_data.get(#id).size
+                    + gs11n::encoder::size_of_nested::<#field_ty>(meta_data.get(#id))
                 });
 
                 decode_stmts.push(quote! {
-                    v.#field_name = <#field_ty>::decode(ptr, ctx)?;
+                    let #target = gs11n::decoder::decode_nested::<#field_ty>(ptr, ctx)?;
                 })
             } else {
                 encode_field_stmts.push(quote! {
-                    gs11n::encoder::encode_field(#id, &self.#field_name, ptr, meta_data.get(#id));
+                    gs11n::encoder::encode_field(#id, &#value, ptr, meta_data.get(#id));
                 });
 
                 size_calculate_stmts.push(quote! {
@@ -140,12 +405,83 @@ impl<'a> Builder<'a> {
                 });
 
                 decode_stmts.push(quote! {
-                    #id => v.#field_name = gs11n::decoder::decode_field(ptr, ctx, is_prefab)?,
+                    #id => #target = Some(gs11n::decoder::decode_field(ptr, ctx, is_prefab)?),
                 })
             }
         }
 
-        return (
+        // a missing field is an error, unless it has a default value, or the struct is
+        // `#[struct_default]`, then it is the value of the field in `Self::default()`, which is only
+        // built when it is needed
+        let struct_default = self.struct_default && variant.is_none();
+        let field_of = |member: &Member| {
+            let binding = binding_of(member);
+            fields
+                .iter()
+                .find(|(_, field)| binding_of(&field.member) == binding)
+        };
+        // `from_default` is whether `__gs11n_default` is built, otherwise the fields which take
+        // their values from it are already unwrapped
+        let field_value = |member: &Member, from_default: bool| {
+            let binding = binding_of(member);
+            match field_of(member) {
+                None if struct_default => quote!(#member: __gs11n_default.#member),
+                None => quote!(#member: Default::default()),
+                Some(_) if compact => quote!(#member: #binding),
+                Some((id, field)) => match &field.options.default {
+                    None if from_default => {
+                        quote!(#member: #binding.unwrap_or(__gs11n_default.#member))
+                    }
+                    None if struct_default => quote!(#member: #binding),
+                    None => quote! {
+                        #member: #binding.ok_or(gs11n::decoder::DecodeError::MissingField(#id))?
+                    },
+                    Some(FieldDefault::Trait) => quote!(#member: #binding.unwrap_or_default()),
+                    Some(FieldDefault::Expr(expr)) => {
+                        quote!(#member: #binding.unwrap_or_else(|| #expr))
+                    }
+                    Some(FieldDefault::Function(path)) => {
+                        quote!(#member: #binding.unwrap_or_else(#path))
+                    }
+                },
+            }
+        };
+        let build = |from_default: bool| {
+            let field_values = members
+                .iter()
+                .map(|member| field_value(member, from_default));
+            quote!(Result::Ok(#constructor { #(#field_values),* }))
+        };
+        let fallbacks: Vec<_> = fields
+            .values()
+            .filter(|field| !compact && field.options.default.is_none())
+            .map(|field| binding_of(&field.member))
+            .collect();
+        let build_value = if !struct_default {
+            build(false)
+        } else if members.iter().any(|member| field_of(member).is_none()) {
+            // members which are not serialized always take their values from the default
+            let value = build(true);
+            quote! {
+                let __gs11n_default = <Self as Default>::default();
+                #value
+            }
+        } else if fallbacks.is_empty() {
+            build(false)
+        } else {
+            let (present, missing) = (build(false), build(true));
+            quote! {
+                match (#(#fallbacks,)*) {
+                    (#(Some(#fallbacks),)*) => #present,
+                    (#(#fallbacks,)*) => {
+                        let __gs11n_default = <Self as Default>::default();
+                        #missing
+                    }
+                }
+            }
+        };
+
+        (
             quote! {
                 #(#encode_field_stmts)*
             },
@@ -156,17 +492,16 @@ impl<'a> Builder<'a> {
                     #(#size_calculate_stmts)* ;
                     meta_data.size = size;
             },
-            if self.compact {
+            if compact {
                 quote! {
-                    let mut v = Self::default();
                     #(#decode_stmts)*
-                    Result::Ok(v)
+                    #build_value
                 }
             } else {
                 quote! {
                     use gs11n::wire_type::WireType;
                     use gs11n::decoder::decode_wired_id;
-                    let mut v = Self::default();
+                    #(#decode_locals)*
                     while (*ptr).lt(&ctx.bounds_checker.get_bound()) {
                         let (id, wire_type) = decode_wired_id(ptr, ctx)?;
                         let is_prefab = wire_type == WireType::Prefab;
@@ -177,9 +512,32 @@ impl<'a> Builder<'a> {
                             }
                         }
                     }
-                    Result::Ok(v)
+                    #build_value
                 }
             },
-        );
+        )
+    }
+}
+
+/// The name of the quantizer of a field, or a field of a variant.
+fn quantizer_of(variant: Option<&Member>, id: usize) -> Ident {
+    match variant {
+        Some(variant) => format_ident!(
+            "__GS11N_QUANTIZER_{}_{}",
+            binding_of(variant)
+                .to_string()
+                .trim_start_matches('_')
+                .to_uppercase(),
+            id
+        ),
+        None => format_ident!("__GS11N_QUANTIZER_{}", id),
+    }
+}
+
+/// The local variable bound to a field of a variant, prefixed so it won't shadow the arguments.
+fn binding_of(member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => format_ident!("__{}", ident),
+        Member::Unnamed(index) => format_ident!("__{}", index.index),
     }
 }
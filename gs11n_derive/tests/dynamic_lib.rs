@@ -9,7 +9,7 @@ impl TypeId for i32 {
 }
 
 #[gs11n_derive::dynamic]
-trait ToString {
+pub trait ToString {
     fn to_string(&self) -> String;
 }
 
@@ -33,6 +33,5 @@ impl ToString for i32 {
 fn get_encode_buffer() -> Vec<u8> {
     let b1: Box<dyn ToString> = Box::new(256i32);
     let encoder = Encoder::from(&b1);
-    let v = encoder.encode();
-    v
+    encoder.encode()
 }
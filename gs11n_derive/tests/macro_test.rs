@@ -1,7 +1,13 @@
-use gs11n::decoder::Decoder;
+use gs11n::bit_vec::BitVec;
+use gs11n::decoder::{ArrayLengthPolicy, DecodeError, Decoder};
 use gs11n::encoder::Encoder;
-use gs11n::{DeSerialization, Serialization};
+use gs11n::wire_type::WireType;
+use gs11n::{DeSerializationOwned, Serialization, WireTypeTrait};
 use gs11n_derive::GS11N;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
 
 #[derive(PartialEq, Debug, GS11N, Default)]
 #[compact]
@@ -12,7 +18,7 @@ struct Color {
 }
 
 #[derive(PartialEq, Debug, GS11N, Default)]
-struct Position<T: Serialization + DeSerialization + Default> {
+struct Position<T: Serialization + DeSerializationOwned + Default> {
     #[serialized(0)]
     x: T,
     #[serialized(1)]
@@ -174,3 +180,836 @@ fn serialization_test() {
     assert_eq!(foo.f_30, foo2.f_30);
     assert_eq!(foo.f_31, foo2.f_31);
 }
+
+#[test]
+fn generic_test() {
+    let position: Position<i32> = Position { x: 1, y: -1 };
+    let encoder = Encoder::from(&position);
+    let real = encoder.encode();
+    assert_eq!(real, vec![0b110_00000, 0x2, 0b110_00001, 0x1]);
+
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Position<i32>>().unwrap(), position);
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+#[struct_default]
+struct Flags {
+    #[serialized(0)]
+    visible: bool,
+    #[serialized(1)]
+    marker: PhantomData<u8>,
+}
+
+#[test]
+fn zero_sized_field_test() {
+    let flags = Flags {
+        visible: true,
+        marker: PhantomData,
+    };
+    let encoder = Encoder::from(&flags);
+    let real = encoder.encode();
+    assert_eq!(real, vec![0b000_00000, 0x1, 0b111_00001, 0x0]);
+
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Flags>().unwrap(), flags);
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct FogFlags {
+    #[serialized(0)]
+    visible: bool,
+    #[serialized(2)]
+    explored: BitVec,
+}
+
+#[test]
+fn bit_vec_field_test() {
+    let flags = FogFlags {
+        visible: true,
+        explored: BitVec::from_elem(1000, true),
+    };
+    let encoder = Encoder::from(&flags);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<FogFlags>().unwrap(), flags);
+
+    // readers which don't know the bits can skip them
+    let decoder = Decoder::from_data(real.as_slice());
+    assert!(decoder.decode::<Flags>().unwrap().visible);
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct Snapshot {
+    #[serialized(0)]
+    id: u32,
+    #[serialized(1, quantize(min = -512.0, max = 512.0, bits = 16))]
+    x: f32,
+    #[serialized(2, quantize(min = 0, max = 360, bits = 12))]
+    yaw: f64,
+}
+
+#[test]
+fn quantize_test() {
+    let snapshot = Snapshot {
+        id: 7,
+        x: 123.456,
+        yaw: 359.9,
+    };
+    let encoder = Encoder::from(&snapshot);
+    let real = encoder.encode();
+    // 16 bits fit in a 3 bytes varint, 12 bits in 2 bytes
+    assert_eq!(real.len(), 2 + 4 + 3);
+
+    let decoder = Decoder::from_data(real.as_slice());
+    let decoded = decoder.decode::<Snapshot>().unwrap();
+    assert_eq!(decoded.id, 7);
+    // half a step, plus the rounding error of f32
+    assert!((decoded.x - snapshot.x).abs() <= 1024.0 / 65535.0 / 2.0 + 1e-4);
+    assert!((decoded.yaw - snapshot.yaw).abs() <= 360.0 / 4095.0 / 2.0);
+
+    // out of range values are clamped
+    let snapshot = Snapshot {
+        id: 0,
+        x: -1000.0,
+        yaw: 400.0,
+    };
+    let real = Encoder::from(&snapshot).encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    let decoded = decoder.decode::<Snapshot>().unwrap();
+    assert_eq!((decoded.x, decoded.yaw), (-512.0, 360.0));
+}
+
+const MAX_TURN: f32 = 180.0;
+
+#[derive(PartialEq, Debug, GS11N)]
+enum Motion {
+    #[serialized(0)]
+    Stop,
+    #[serialized(1)]
+    Turn {
+        #[serialized(0, quantize(min = -MAX_TURN, max = MAX_TURN, bits = 8))]
+        angle: f32,
+    },
+}
+
+#[test]
+fn quantize_variant_test() {
+    let motion = Motion::Turn { angle: 90.0 };
+    let real = Encoder::from(&motion).encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    match decoder.decode::<Motion>().unwrap() {
+        Motion::Turn { angle } => assert!((angle - 90.0).abs() <= 360.0 / 255.0 / 2.0),
+        Motion::Stop => panic!("wrong variant"),
+    }
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct Transform {
+    #[serialized(0)]
+    position: Position<i32>,
+    #[serialized(1)]
+    scale: f32,
+}
+
+#[test]
+fn nested_struct_test() {
+    let transform = Transform {
+        position: Position { x: 1, y: -1 },
+        scale: 2.0,
+    };
+    let encoder = Encoder::from(&transform);
+    let real = encoder.encode();
+
+    // fields after a nested struct belong to the outer struct
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Transform>().unwrap(), transform);
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+struct Tile {
+    #[serialized(0)]
+    kind: u8,
+    #[serialized(1)]
+    walkable: bool,
+}
+
+#[test]
+fn struct_in_sequence_test() {
+    // a struct is prefixed by its size when nested without a field id
+    let tiles = vec![Tile {
+        kind: 1,
+        walkable: true,
+    }];
+    let real = Encoder::from(&tiles).encode();
+    assert_eq!(real, vec![1, 4, 0b110_00000, 1, 0b000_00001, 1]);
+
+    let mut grid = HashMap::new();
+    grid.insert(
+        (0, 0),
+        Tile {
+            kind: 1,
+            walkable: true,
+        },
+    );
+    grid.insert(
+        (0, -1),
+        Tile {
+            kind: 2,
+            walkable: false,
+        },
+    );
+    grid.insert(
+        (3, 7),
+        Tile {
+            kind: 3,
+            walkable: true,
+        },
+    );
+    let real = Encoder::from(&grid).encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<HashMap<(i32, i32), Tile>>().unwrap(), grid);
+
+    let pair = (
+        Tile {
+            kind: 4,
+            walkable: false,
+        },
+        5u8,
+    );
+    let real = Encoder::from(&pair).encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<(Tile, u8)>().unwrap(), pair);
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct ChatMessage<'a> {
+    #[serialized(0)]
+    sender: &'a str,
+    #[serialized(1)]
+    text: &'a str,
+    #[serialized(2)]
+    attachment: &'a [u8],
+}
+
+#[test]
+fn borrowed_field_test() {
+    let message = ChatMessage {
+        sender: "alice",
+        text: "gg",
+        attachment: &[1, 2, 0xFF],
+    };
+    let encoder = Encoder::from(&message);
+    let real = encoder.encode();
+
+    let decoder = Decoder::from_data(real.as_slice());
+    let decode_message = decoder.decode::<ChatMessage>().unwrap();
+    assert_eq!(decode_message, message);
+    assert!(real.as_ptr_range().contains(&decode_message.text.as_ptr()));
+    assert!(real
+        .as_ptr_range()
+        .contains(&decode_message.attachment.as_ptr()));
+}
+
+#[derive(GS11N, Default)]
+struct Node {
+    #[serialized(0)]
+    name: String,
+    #[serialized(1)]
+    parent: RefCell<Weak<RefCell<Node>>>,
+    #[serialized(2)]
+    children: Vec<Rc<RefCell<Node>>>,
+}
+
+#[test]
+fn shared_node_test() {
+    let root = Rc::new(RefCell::new(Node::default()));
+    root.borrow_mut().name = String::from("root");
+    for name in ["left", "right"] {
+        let child = Rc::new(RefCell::new(Node::default()));
+        child.borrow_mut().name = String::from(name);
+        *child.borrow().parent.borrow_mut() = Rc::downgrade(&root);
+        root.borrow_mut().children.push(child);
+    }
+    let shared = root.borrow().children[1].clone();
+    root.borrow_mut().children.push(shared);
+
+    let encoder = Encoder::from_shared(&root);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice()).with_shared_references();
+    let decode_root: Rc<RefCell<Node>> = decoder.decode().unwrap();
+
+    let decode_root_ref = decode_root.borrow();
+    assert_eq!(decode_root_ref.name, "root");
+    let children = &decode_root_ref.children;
+    assert_eq!(children.len(), 3);
+    assert_eq!(children[0].borrow().name, "left");
+    assert_eq!(children[1].borrow().name, "right");
+    assert!(Rc::ptr_eq(&children[1], &children[2]));
+    for child in children {
+        let parent = child.borrow().parent.borrow().upgrade().unwrap();
+        assert!(Rc::ptr_eq(&parent, &decode_root));
+    }
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+enum Reward {
+    #[serialized(0)]
+    Gold(u32),
+    #[serialized(1)]
+    Items(Vec<Color>),
+}
+
+impl Default for Reward {
+    fn default() -> Self {
+        Reward::Gold(0)
+    }
+}
+
+fn colors() -> Vec<Color> {
+    (0..50).map(|i| Color { r: i, g: 1, b: 2 }).collect()
+}
+
+#[test]
+fn enum_test() {
+    let items = Reward::Items(colors());
+    let encoder = Encoder::from(&items);
+    let real = encoder.encode();
+    // id, element count, then the elements
+    assert_eq!(real.len(), 1 + 1 + 50 * 3);
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Reward>().unwrap(), items);
+
+    // a `Result` is encoded like an enum
+    let result: Result<u32, Vec<Color>> = Ok(1000);
+    assert_eq!(
+        Encoder::from(&result).encode(),
+        Encoder::from(&Reward::Gold(1000)).encode()
+    );
+    let decoder = Decoder::from_data(real.as_slice());
+    let result: Result<u32, Vec<Color>> = decoder.decode().unwrap();
+    assert_eq!(result, Err(colors()));
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+#[compact]
+struct Meters(f32);
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct EntityId(#[serialized(0)] u32, #[serialized(1)] u32);
+
+#[derive(PartialEq, Debug, GS11N)]
+struct Spawned;
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct Entity {
+    #[serialized(0)]
+    id: EntityId,
+    #[serialized(1)]
+    height: Meters,
+    #[serialized(2)]
+    spawned: Option<Spawned>,
+}
+
+#[test]
+fn tuple_struct_test() {
+    let encoder = Encoder::from(&Meters(1.5));
+    assert_eq!(encoder.encode(), 1.5f32.to_le_bytes().to_vec());
+
+    let encoder = Encoder::from(&EntityId(7, 300));
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<EntityId>().unwrap(), EntityId(7, 300));
+
+    // a unit struct has no data
+    assert!(Encoder::from(&Spawned).encode().is_empty());
+    let decoder = Decoder::from_data(&[]);
+    assert_eq!(decoder.decode::<Spawned>().unwrap(), Spawned);
+
+    let entity = Entity {
+        id: EntityId(1, 2),
+        height: Meters(1.8),
+        spawned: Some(Spawned),
+    };
+    let encoder = Encoder::from(&entity);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Entity>().unwrap(), entity);
+}
+
+#[derive(PartialEq, Debug, GS11N, Clone, Copy)]
+enum Faction {
+    Red,
+    Blue,
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+enum AiState {
+    Idle = 3,
+    Patrol,
+    #[serialized(200)]
+    Chase,
+    Dead = 10,
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+enum Command {
+    Stop,
+    #[serialized(5)]
+    MoveTo(Position<i32>),
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct Unit {
+    #[serialized(0)]
+    faction: Option<Faction>,
+    #[serialized(1)]
+    hp: u32,
+}
+
+#[test]
+fn unit_variant_test() {
+    // a fieldless enum is encoded as a varint
+    assert_eq!(Faction::WIRE_TYPE, WireType::Varint);
+    assert_eq!(Encoder::from(&Faction::Blue).encode(), vec![1]);
+    let states = vec![
+        AiState::Idle,
+        AiState::Patrol,
+        AiState::Chase,
+        AiState::Dead,
+    ];
+    let encoder = Encoder::from(&states);
+    let real = encoder.encode();
+    assert_eq!(real, vec![4, 3, 4, 0xC8, 0x01, 10]);
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Vec<AiState>>().unwrap(), states);
+
+    let decoder = Decoder::from_data(&[2]);
+    assert_eq!(decoder.decode::<Faction>(), Err(DecodeError::InvalidType));
+
+    let unit = Unit {
+        faction: Some(Faction::Blue),
+        hp: 100,
+    };
+    let encoder = Encoder::from(&unit);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Unit>().unwrap(), unit);
+
+    // an enum with fields is still length delimited
+    assert_eq!(Command::WIRE_TYPE, WireType::LengthDelimited);
+    let commands = vec![Command::Stop, Command::MoveTo(Position { x: 1, y: 2 })];
+    let encoder = Encoder::from(&commands);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Vec<Command>>().unwrap(), commands);
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+enum GameEvent {
+    Spawn,
+    Move {
+        x: f32,
+        y: f32,
+    },
+    Hit(EntityId, u32),
+    #[serialized(10)]
+    Heal {
+        #[serialized(1)]
+        amount: u32,
+        #[serialized(0, quantize(min = 0.0, max = 1.0, bits = 8))]
+        ratio: f32,
+    },
+}
+
+// a newer version which adds a field to `Move`
+#[derive(PartialEq, Debug, GS11N)]
+enum GameEventV2 {
+    Spawn,
+    Move { x: f32, y: f32, z: f32 },
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct EventLog {
+    #[serialized(0)]
+    event: Option<GameEvent>,
+    #[serialized(1)]
+    frame: u64,
+}
+
+#[test]
+fn variant_fields_test() {
+    let events = vec![
+        GameEvent::Move { x: 1.0, y: -2.0 },
+        GameEvent::Spawn,
+        GameEvent::Hit(EntityId(3, 4), 25),
+        GameEvent::Heal {
+            amount: 50,
+            ratio: 1.0,
+        },
+    ];
+    for event in events {
+        let encoder = Encoder::from(&event);
+        let real = encoder.encode();
+        let decoder = Decoder::from_data(real.as_slice());
+        assert_eq!(decoder.decode::<GameEvent>().unwrap(), event);
+    }
+
+    // id, size, then x and y as fields with their own ids
+    let event = GameEvent::Move { x: 1.0, y: -2.0 };
+    let encoder = Encoder::from(&event);
+    assert_eq!(encoder.encode().len(), 1 + 1 + 5 + 5);
+
+    // variants with fields end at their sizes, so they can be followed by other elements
+    let events = vec![
+        GameEvent::Move { x: 1.0, y: -2.0 },
+        GameEvent::Hit(EntityId(1, 2), 3),
+        GameEvent::Spawn,
+    ];
+    let encoder = Encoder::from(&events);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Vec<GameEvent>>().unwrap(), events);
+
+    // old readers skip the new fields
+    let event = GameEventV2::Move {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    let encoder = Encoder::from(&event);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(
+        decoder.decode::<GameEvent>().unwrap(),
+        GameEvent::Move { x: 1.0, y: 2.0 }
+    );
+
+    // fields after the event belong to the struct
+    let log = EventLog {
+        event: Some(GameEvent::Hit(EntityId(1, 2), 3)),
+        frame: 600,
+    };
+    let encoder = Encoder::from(&log);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<EventLog>().unwrap(), log);
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+#[length_delimited]
+enum NetMessage {
+    Ping,
+    Chat(String),
+    Move {
+        x: f32,
+        y: f32,
+    },
+    #[serialized(other)]
+    Unknown(usize, Vec<u8>),
+}
+
+// a newer version which adds a variant
+#[derive(PartialEq, Debug, GS11N)]
+#[length_delimited]
+enum NetMessageV2 {
+    Ping,
+    Chat(String),
+    Move { x: f32, y: f32 },
+    Trade { item: u32, count: u32 },
+}
+
+#[derive(PartialEq, Debug, GS11N, Default)]
+struct Packet {
+    #[serialized(0)]
+    message: Option<NetMessage>,
+    #[serialized(1)]
+    sequence: u32,
+}
+
+#[test]
+fn length_delimited_enum_test() {
+    assert_eq!(NetMessage::WIRE_TYPE, WireType::LengthDelimited);
+    // id, then the size of the data
+    assert_eq!(Encoder::from(&NetMessage::Ping).encode(), vec![0, 0]);
+    let chat = NetMessage::Chat(String::from("gg"));
+    let encoder = Encoder::from(&chat);
+    let real = encoder.encode();
+    assert_eq!(real, vec![1, 3, 2, b'g', b'g']);
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<NetMessage>().unwrap(), chat);
+
+    let message = NetMessage::Move { x: 1.0, y: 2.0 };
+    let encoder = Encoder::from(&message);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<NetMessage>().unwrap(), message);
+
+    // an old reader keeps the unknown variant, and writes it back without losing data
+    let trade = NetMessageV2::Trade {
+        item: 7,
+        count: 300,
+    };
+    let encoder = Encoder::from(&trade);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    let unknown = decoder.decode::<NetMessage>().unwrap();
+    assert_eq!(unknown, NetMessage::Unknown(3, real[2..].to_vec()));
+    let encoder = Encoder::from(&unknown);
+    let rewritten = encoder.encode();
+    assert_eq!(rewritten, real);
+    let decoder = Decoder::from_data(rewritten.as_slice());
+    assert_eq!(decoder.decode::<NetMessageV2>().unwrap(), trade);
+
+    let packet = Packet {
+        message: Some(NetMessage::Unknown(9, vec![1, 2, 3])),
+        sequence: 42,
+    };
+    let encoder = Encoder::from(&packet);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Packet>().unwrap(), packet);
+
+    // a corrupted size fails without computing an address out of the data
+    let huge_size = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F];
+    let corrupted = [&[1][..], &huge_size, b"gg"].concat();
+    let decoder = Decoder::from_data(corrupted.as_slice());
+    assert_eq!(
+        decoder.decode::<NetMessage>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+    // an unknown field with id 7 is skipped by its size
+    let corrupted = [&[0b111_00111][..], &huge_size, b"gg"].concat();
+    let decoder = Decoder::from_data(corrupted.as_slice());
+    assert_eq!(
+        decoder.decode::<Packet>(),
+        Err(DecodeError::DecodeOutOfBounds)
+    );
+}
+
+// no `Default` is needed
+#[derive(PartialEq, Debug, GS11N)]
+#[compact]
+struct Handle(u32);
+
+#[derive(PartialEq, Debug, GS11N)]
+struct Character {
+    #[serialized(0)]
+    name: String,
+    #[serialized(1)]
+    handle: Handle,
+    #[serialized(2, default)]
+    level: u32,
+    cache: Vec<u32>,
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+struct CharacterV0 {
+    #[serialized(0)]
+    name: String,
+    #[serialized(1)]
+    handle: Handle,
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+struct NameOnly {
+    #[serialized(0)]
+    name: String,
+}
+
+#[test]
+fn missing_field_test() {
+    let character = Character {
+        name: String::from("orc"),
+        handle: Handle(9),
+        level: 3,
+        cache: vec![1, 2],
+    };
+    let encoder = Encoder::from(&character);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    let decoded = decoder.decode::<Character>().unwrap();
+    assert_eq!(decoded.handle, Handle(9));
+    assert_eq!(decoded.level, 3);
+    // fields which are not serialized are default
+    assert!(decoded.cache.is_empty());
+
+    let old = CharacterV0 {
+        name: String::from("orc"),
+        handle: Handle(9),
+    };
+    let encoder = Encoder::from(&old);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Character>().unwrap().level, 0);
+
+    let decoder = Decoder::from_data(&[]);
+    assert_eq!(
+        decoder.decode::<CharacterV0>(),
+        Err(DecodeError::MissingField(0))
+    );
+    let name_only = NameOnly {
+        name: String::from("orc"),
+    };
+    let encoder = Encoder::from(&name_only);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(
+        decoder.decode::<Character>(),
+        Err(DecodeError::MissingField(1))
+    );
+
+    // so are fields of variants
+    let decoder = Decoder::from_data(&[1, 5, 0b010_00000, 0, 0, 0x80, 0x3F]);
+    assert_eq!(
+        decoder.decode::<GameEvent>(),
+        Err(DecodeError::MissingField(1))
+    );
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+#[struct_default]
+struct Settings {
+    #[serialized(0)]
+    volume: u8,
+    #[serialized(1)]
+    difficulty: String,
+    #[serialized(2, default)]
+    muted: bool,
+    session: u32,
+}
+
+// an older version without the volume
+#[derive(PartialEq, Debug, GS11N)]
+struct SettingsV0 {
+    #[serialized(1)]
+    difficulty: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            volume: 80,
+            difficulty: String::from("normal"),
+            muted: true,
+            session: 1,
+        }
+    }
+}
+
+#[test]
+fn struct_default_test() {
+    // missing fields and fields which are not serialized take the values of `Settings::default()`,
+    // unless the field has its own default
+    let old = SettingsV0 {
+        difficulty: String::from("hard"),
+    };
+    let encoder = Encoder::from(&old);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(
+        decoder.decode::<Settings>().unwrap(),
+        Settings {
+            volume: 80,
+            difficulty: String::from("hard"),
+            muted: false,
+            session: 1,
+        }
+    );
+
+    // arrays of it can be padded
+    let decoder = Decoder::from_data(&[1, 2, 0b000_00000, 10])
+        .with_array_length_policy(ArrayLengthPolicy::Pad);
+    let settings = decoder.decode::<[Settings; 2]>().unwrap();
+    assert_eq!(settings[0].volume, 10);
+    assert_eq!(settings[1], Settings::default());
+}
+
+thread_local! {
+    static DEFAULTS_BUILT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+#[struct_default]
+struct LevelInfo {
+    #[serialized(0)]
+    name: String,
+    #[serialized(1)]
+    seed: u64,
+}
+
+impl Default for LevelInfo {
+    fn default() -> Self {
+        DEFAULTS_BUILT.with(|built| built.set(built.get() + 1));
+        LevelInfo {
+            name: String::from("untitled"),
+            seed: 7,
+        }
+    }
+}
+
+#[test]
+fn lazy_struct_default_test() {
+    // the default is only built when a field is missing
+    let level = LevelInfo {
+        name: String::from("swamp"),
+        seed: 42,
+    };
+    let real = Encoder::from(&level).encode();
+    DEFAULTS_BUILT.with(|built| built.set(0));
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<LevelInfo>().unwrap(), level);
+    assert_eq!(DEFAULTS_BUILT.with(|built| built.get()), 0);
+
+    let decoder = Decoder::from_data(&real[..real.len() - 2]);
+    assert_eq!(
+        decoder.decode::<LevelInfo>().unwrap(),
+        LevelInfo {
+            name: String::from("swamp"),
+            seed: 7,
+        }
+    );
+    assert_eq!(DEFAULTS_BUILT.with(|built| built.get()), 1);
+}
+
+fn default_title() -> String {
+    String::from("recruit")
+}
+
+#[derive(PartialEq, Debug, GS11N)]
+struct Stats {
+    #[serialized(0)]
+    health: u32,
+    #[serialized(1, default = 100)]
+    max_health: u32,
+    #[serialized(2, default = "default_title")]
+    title: String,
+    #[serialized(3, default = 0.5, quantize(min = 0.0, max = 1.0, bits = 8))]
+    armor: f32,
+}
+
+#[test]
+fn default_value_test() {
+    let stats = Stats {
+        health: 30,
+        max_health: 50,
+        title: String::from("captain"),
+        armor: 1.0,
+    };
+    let encoder = Encoder::from(&stats);
+    let real = encoder.encode();
+    let decoder = Decoder::from_data(real.as_slice());
+    assert_eq!(decoder.decode::<Stats>().unwrap(), stats);
+
+    // old data which only has the health
+    let decoder = Decoder::from_data(&[0b000_00000, 30]);
+    assert_eq!(
+        decoder.decode::<Stats>().unwrap(),
+        Stats {
+            health: 30,
+            max_health: 100,
+            title: String::from("recruit"),
+            armor: 0.5,
+        }
+    );
+}
@@ -58,6 +58,78 @@ fn invalid_attr_test() {
     );
 }
 
+#[test]
+fn invalid_quantize_test() {
+    let ts = TokenStream::from_str(
+        "#[derive(GS11N)]
+            struct Foo {
+                #[serialized(0, quantize(min = 0.0, max = 1.0, bits = 33))]
+                i: f32,
+                #[serialized(1, quantize(min = 0.0, max = 1.0))]
+                j: f32,
+                #[serialized(2, packed)]
+                k: f32,
+                #[serialized(3, quantize(min = 1.0, max = -1, bits = 8))]
+                l: f32,
+                #[serialized(4, quantize(min = (0.5), max = 0.5, bits = 8))]
+                m: f32,
+            }",
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        concat!(
+            r#"compile_error ! ("quantize bits must be in 1..=32") ; "#,
+            r#"compile_error ! ("unrecognized option, expect `default`, `default = ..` or `quantize(min = .., max = .., bits = ..)`") ; "#,
+            r#"compile_error ! ("unrecognized option, expect `default`, `default = ..` or `quantize(min = .., max = .., bits = ..)`") ; "#,
+            r#"compile_error ! ("quantize range must have min < max") ; "#,
+            r#"compile_error ! ("quantize range must have min < max") ; "#,
+            r#"compile_error ! ("no serializable field found") ;"#
+        ),
+        Builder::from(&input).build().to_string()
+    );
+}
+
+#[test]
+fn invalid_default_test() {
+    let ts = TokenStream::from_str(
+        r#"#[derive(GS11N)]
+            struct Foo {
+                #[serialized(0, default = "not a path")]
+                i: i32,
+                #[serialized(1, default, default = 1)]
+                j: i32,
+            }"#,
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        concat!(
+            r#"compile_error ! ("unrecognized option, expect `default`, `default = ..` or `quantize(min = .., max = .., bits = ..)`") ; "#,
+            r#"compile_error ! ("unrecognized option, expect `default`, `default = ..` or `quantize(min = .., max = .., bits = ..)`") ; "#,
+            r#"compile_error ! ("no serializable field found") ;"#,
+        ),
+        Builder::from(&input).build().to_string()
+    );
+
+    let ts = TokenStream::from_str(
+        "#[derive(GS11N)]
+            enum Foo {
+                #[serialized(0, default)]
+                A(i32),
+            }",
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        concat!(
+            r#"compile_error ! ("default is only supported on fields") ; "#,
+            r#"compile_error ! ("no serializable field found") ;"#,
+        ),
+        Builder::from(&input).build().to_string()
+    );
+}
+
 #[test]
 fn no_serializable_field_test() {
     let ts = TokenStream::from_str(
@@ -76,6 +148,115 @@ fn no_serializable_field_test() {
     );
 }
 
+#[test]
+fn variant_id_test() {
+    let ts = TokenStream::from_str(
+        "#[derive(GS11N)]
+            enum Foo {
+                A = 1 + 1,
+                B,
+                #[serialized(3)]
+                C = X,
+                D,
+            }",
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        concat!(
+            r#"compile_error ! ("variant id unknown, add `#[serialized(id)]` or an int literal discriminant") ; "#,
+            r#"compile_error ! ("variant id unknown, add `#[serialized(id)]` or an int literal discriminant") ; "#,
+            r#"compile_error ! ("variant id unknown, add `#[serialized(id)]` or an int literal discriminant") ;"#,
+        ),
+        Builder::from(&input).build().to_string()
+    );
+
+    let ts = TokenStream::from_str(
+        "#[derive(GS11N)]
+            enum Foo {
+                #[serialized(1)]
+                A,
+                B,
+            }",
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        r#"compile_error ! ("field id already used.") ;"#,
+        Builder::from(&input).build().to_string()
+    );
+}
+
+#[test]
+fn other_variant_test() {
+    let ts = TokenStream::from_str(
+        "#[derive(GS11N)]
+            enum Foo {
+                A(u32),
+                #[serialized(other)]
+                B(usize, Vec<u8>),
+            }",
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        r#"compile_error ! ("other variant requires `#[length_delimited]` on the enum") ;"#,
+        Builder::from(&input).build().to_string()
+    );
+
+    let ts = TokenStream::from_str(
+        "#[derive(GS11N)]
+            #[length_delimited]
+            enum Foo {
+                A(u32),
+                #[serialized(other)]
+                B(Vec<u8>),
+                #[serialized(other)]
+                C(usize, Vec<u8>),
+                #[serialized(other)]
+                D(usize, Vec<u8>),
+            }",
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        concat!(
+            r#"compile_error ! ("other variant must have two fields, the id and the data") ; "#,
+            r#"compile_error ! ("other variant already declared") ;"#,
+        ),
+        Builder::from(&input).build().to_string()
+    );
+
+    let ts = TokenStream::from_str(
+        "#[derive(GS11N)]
+            #[length_delimited]
+            struct Foo {
+                #[serialized(0)]
+                i: i32,
+            }",
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        r#"compile_error ! ("length_delimited attribute is only supported on enums") ;"#,
+        Builder::from(&input).build().to_string()
+    );
+
+    let ts = TokenStream::from_str(
+        "#[derive(GS11N)]
+            #[struct_default]
+            enum Foo {
+                A,
+            }",
+    )
+    .unwrap();
+    let input = syn::parse2::<DeriveInput>(ts).unwrap();
+    assert_eq!(
+        r#"compile_error ! ("struct_default attribute is only supported on structs") ;"#,
+        Builder::from(&input).build().to_string()
+    );
+}
+
 #[test]
 // Used to generate code manually
 fn derive_test() {
@@ -92,7 +273,7 @@ fn derive_test() {
     )
     .unwrap();
     let input = syn::parse2::<DeriveInput>(ts).unwrap();
-    println!("{}", Builder::from(&input).build().to_string());
+    println!("{}", Builder::from(&input).build());
 }
 
 #[test]